@@ -0,0 +1,397 @@
+/*!
+The `#[derive(NumId)]` macro of the [numid](https://docs.rs/numid) crate, use it through the `derive`
+feature of `numid` :
+
+```ignore
+use numid::NumId;
+
+#[derive(NumId)]
+#[numid(init = 10, repr = u32, clone = "reproduce", step = 2)]
+struct MyId(u32);
+```
+
+The derive generates the same methods and trait implementations as `numid!`, including `PartialEq`, `Eq`,
+`PartialOrd`, `Ord`, `Hash` and `Debug` : these traits must not be derived for the `struct`.
+
+The options of the `numid` attribute are :
+- `init = EXPR` : the initial value (0 if not defined)
+- `repr = TYPE` : the integer type of the value, must be the type of the field if defined
+- `clone = "clone" | "new" | "reproduce"` : the behavior of `clone()`, see `@CloneIsClone`...
+- `overflow = "panic" | "saturate" | "wrap"` : the overflow policy, see `@OverflowPanic`...
+- `parse = "any" | "lower" | "create"` : the values accepted by `FromStr`, see `@ParseAny`...
+- `prefix = "..."` : the prefix used by `Display` and `FromStr`, see `@Prefix("...")`
+- `step = EXPR` : see `@Step(N)`
+- `pool` : see `@Pool`
+- `thread_cached = EXPR` : see `@ThreadCached(N)`
+- `source = PATH` : the `static` storing the "current value", see `@Source(path::to::SOURCE)`
+- `serde_replace_current` and `serde_as_string` : see `@SerdeReplaceCurrent` and `@SerdeAsString`
+*/
+
+extern crate proc_macro;
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::meta::ParseNestedMeta;
+use syn::spanned::Spanned;
+use syn::{Data, DeriveInput, Error, Expr, ExprLit, Fields, Lit, LitStr, Result, Type};
+
+/// The integer types accepted as value of an id.
+const SUPPORTED_REPRS: &[&str] = &[
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+];
+
+/// Implement `NumId` for a tuple struct with a single integer field, see the crate documentation.
+#[proc_macro_derive(NumId, attributes(numid))]
+pub fn derive_num_id(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// The options of the `numid` attribute, with the span of the key for the duplicate options.
+#[derive(Default)]
+struct Options {
+    init: Option<Expr>,
+    repr: Option<Type>,
+    options: Vec<(String, TokenStream, Span)>,
+}
+
+impl Options {
+    fn push(&mut self, meta: &ParseNestedMeta, name: &str, args: TokenStream) -> Result<()> {
+        let group = |name: &str| -> &'static str {
+            match name {
+                "CloneIsClone" | "CloneIsNew" | "CloneIsReproduce" => "clone",
+                "OverflowPanic" | "OverflowSaturate" | "OverflowWrap" => "overflow",
+                "Step" => "step",
+                "Pool" => "pool",
+                "ThreadCached" => "thread_cached",
+                "SerdeReplaceCurrent" => "serde_replace_current",
+                "ParseAny" | "ParseLower" | "ParseCreate" => "parse",
+                "Prefix" => "prefix",
+                "Source" => "source",
+                "SerdeAsString" => "serde_as_string",
+                _ => unreachable!("numid option `{}` without group", name),
+            }
+        };
+        if self
+            .options
+            .iter()
+            .any(|(other, _, _)| group(other) == group(name))
+        {
+            return Err(meta.error(format!("duplicate numid option `{}`", group(name))));
+        }
+        self.options.push((name.to_owned(), args, meta.path.span()));
+        Ok(())
+    }
+
+    fn parse(&mut self, meta: ParseNestedMeta) -> Result<()> {
+        let key = match meta.path.get_ident() {
+            Some(ident) => ident.to_string(),
+            None => return Err(meta.error("expected a numid option")),
+        };
+        let choice =
+            |meta: &ParseNestedMeta, choices: &[(&str, &'static str)]| -> Result<&'static str> {
+                let value: LitStr = meta.value()?.parse()?;
+                let found = choices.iter().find(|(choice, _)| value.value() == *choice);
+                match found {
+                    Some((_, name)) => Ok(name),
+                    None => {
+                        let expected: Vec<String> =
+                            choices.iter().map(|(c, _)| format!("\"{}\"", c)).collect();
+                        Err(Error::new(
+                            value.span(),
+                            format!(
+                                "unknown `{}` value, expected one of {}",
+                                key,
+                                expected.join(", ")
+                            ),
+                        ))
+                    }
+                }
+            };
+        match key.as_str() {
+            "init" => {
+                if self.init.is_some() {
+                    return Err(meta.error("duplicate numid option `init`"));
+                }
+                self.init = Some(meta.value()?.parse()?);
+            }
+            "repr" => {
+                if self.repr.is_some() {
+                    return Err(meta.error("duplicate numid option `repr`"));
+                }
+                self.repr = Some(meta.value()?.parse()?);
+            }
+            "clone" => {
+                let name = choice(
+                    &meta,
+                    &[
+                        ("clone", "CloneIsClone"),
+                        ("new", "CloneIsNew"),
+                        ("reproduce", "CloneIsReproduce"),
+                    ],
+                )?;
+                self.push(&meta, name, TokenStream::new())?;
+            }
+            "overflow" => {
+                let name = choice(
+                    &meta,
+                    &[
+                        ("panic", "OverflowPanic"),
+                        ("saturate", "OverflowSaturate"),
+                        ("wrap", "OverflowWrap"),
+                    ],
+                )?;
+                self.push(&meta, name, TokenStream::new())?;
+            }
+            "parse" => {
+                let name = choice(
+                    &meta,
+                    &[
+                        ("any", "ParseAny"),
+                        ("lower", "ParseLower"),
+                        ("create", "ParseCreate"),
+                    ],
+                )?;
+                self.push(&meta, name, TokenStream::new())?;
+            }
+            "prefix" => {
+                let value: LitStr = meta.value()?.parse()?;
+                self.push(&meta, "Prefix", quote!(#value))?;
+            }
+            "step" | "thread_cached" => {
+                let value: Expr = meta.value()?.parse()?;
+                if is_zero(&value) {
+                    return Err(meta.error(format!("`{}` must be positive", key)));
+                }
+                let name = if key == "step" {
+                    "Step"
+                } else {
+                    "ThreadCached"
+                };
+                self.push(&meta, name, quote!(#value))?;
+            }
+            "source" => {
+                let value: syn::Path = meta.value()?.parse()?;
+                self.push(&meta, "Source", quote!(#value))?;
+            }
+            "pool" => self.push(&meta, "Pool", TokenStream::new())?,
+            "serde_replace_current" => {
+                self.push(&meta, "SerdeReplaceCurrent", TokenStream::new())?
+            }
+            "serde_as_string" => self.push(&meta, "SerdeAsString", TokenStream::new())?,
+            _ => return Err(meta.error(format!("unknown numid option `{}`", key))),
+        }
+        Ok(())
+    }
+}
+
+/// Return true if `expr` is the integer literal 0, the other expressions are checked by `numid!`.
+fn is_zero(expr: &Expr) -> bool {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(int), ..
+        }) => int.base10_digits() == "0",
+        Expr::Group(group) => is_zero(&group.expr),
+        Expr::Paren(paren) => is_zero(&paren.expr),
+        _ => false,
+    }
+}
+
+/// Return the name of the integer type if it is a supported representation.
+fn repr_name(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Path(path) if path.qself.is_none() => {
+            let ident = path.path.get_ident()?.to_string();
+            if SUPPORTED_REPRS.contains(&ident.as_str()) {
+                Some(ident)
+            } else {
+                None
+            }
+        }
+        Type::Group(group) => repr_name(&group.elem),
+        _ => None,
+    }
+}
+
+fn unsupported_repr(ty: &Type) -> Error {
+    Error::new(
+        ty.span(),
+        format!(
+            "unsupported numid representation `{}`, expected one of {}",
+            quote!(#ty),
+            SUPPORTED_REPRS.join(", ")
+        ),
+    )
+}
+
+/// Generate the implementation of `#[derive(NumId)]`.
+fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let name = &input.ident;
+
+    if !input.generics.params.is_empty() {
+        return Err(Error::new(
+            input.generics.span(),
+            "`#[derive(NumId)]` doesn't support generic structs",
+        ));
+    }
+    let field = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &fields.unnamed[0],
+            fields => {
+                return Err(Error::new(
+                    fields.span(),
+                    "`#[derive(NumId)]` expects a tuple struct with a single integer field, like `struct Id(u32);`",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                name.span(),
+                "`#[derive(NumId)]` can only be used on a tuple struct, like `struct Id(u32);`",
+            ))
+        }
+    };
+
+    let mut options = Options::default();
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("numid"))
+    {
+        attr.parse_nested_meta(|meta| options.parse(meta))?;
+    }
+
+    let ty = &field.ty;
+    let field_repr = repr_name(ty);
+    if let Some(repr) = &options.repr {
+        let repr_str = repr_name(repr).ok_or_else(|| unsupported_repr(repr))?;
+        if field_repr.as_ref() != Some(&repr_str) {
+            return Err(Error::new(
+                ty.span(),
+                format!(
+                    "the field must have the type of the numid representation `{}`",
+                    repr_str
+                ),
+            ));
+        }
+    } else if field_repr.is_none() {
+        return Err(unsupported_repr(ty));
+    }
+
+    let init = match &options.init {
+        Some(init) => quote!(#init),
+        None => quote!(0),
+    };
+    let mattrs = options.options.iter().map(|(option, args, span)| {
+        let option = syn::Ident::new(option, *span);
+        quote!(#option [#args])
+    });
+
+    Ok(quote! {
+        ::numid::numid!{@__body [#(#mattrs)*] #name(#ty) -> #init}
+
+        impl ::core::cmp::PartialEq for #name {
+            #[inline]
+            fn eq(&self, other: &#name) -> bool {
+                self.0 == other.0
+            }
+        }
+
+        impl ::core::cmp::Eq for #name {}
+
+        impl ::core::cmp::PartialOrd for #name {
+            #[inline]
+            fn partial_cmp(&self, other: &#name) -> ::core::option::Option<::core::cmp::Ordering> {
+                ::core::option::Option::Some(::core::cmp::Ord::cmp(self, other))
+            }
+        }
+
+        impl ::core::cmp::Ord for #name {
+            #[inline]
+            fn cmp(&self, other: &#name) -> ::core::cmp::Ordering {
+                ::core::cmp::Ord::cmp(&self.0, &other.0)
+            }
+        }
+
+        impl ::core::hash::Hash for #name {
+            #[inline]
+            fn hash<H: ::core::hash::Hasher>(&self, state: &mut H) {
+                ::core::hash::Hash::hash(&self.0, state)
+            }
+        }
+
+        impl ::core::fmt::Debug for #name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.debug_tuple(::core::stringify!(#name)).field(&self.0).finish()
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::expand;
+
+    fn error(input: &str) -> String {
+        let input = syn::parse_str(input).unwrap();
+        expand(&input).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn tests_expand() {
+        let input = syn::parse_str(
+            "#[numid(init = 10, repr = u32, clone = \"reproduce\", step = 2)] struct Id(u32);",
+        )
+        .unwrap();
+        let output = expand(&input).unwrap().to_string();
+        assert!(output.contains("@ __body [CloneIsReproduce [] Step [2]] Id (u32) -> 10"));
+
+        let input = syn::parse_str("#[numid(source = crate::SOURCE)] struct Id(u64);").unwrap();
+        let output = expand(&input).unwrap().to_string();
+        assert!(output.contains("@ __body [Source [crate :: SOURCE]] Id (u64) -> 0"));
+    }
+
+    #[test]
+    fn tests_errors() {
+        assert_eq!(
+            error("#[numid(repr = f32)] struct Id(f32);"),
+            "unsupported numid representation `f32`, expected one of u8, u16, u32, u64, u128, usize, \
+             i8, i16, i32, i64, i128, isize"
+        );
+        assert_eq!(
+            error("struct Id(String);"),
+            "unsupported numid representation `String`, expected one of u8, u16, u32, u64, u128, usize, \
+             i8, i16, i32, i64, i128, isize"
+        );
+        assert_eq!(
+            error("#[numid(repr = u64)] struct Id(u32);"),
+            "the field must have the type of the numid representation `u64`"
+        );
+        assert_eq!(
+            error("#[numid(start = 1)] struct Id(u32);"),
+            "unknown numid option `start`"
+        );
+        assert_eq!(
+            error("#[numid(clone = \"copy\")] struct Id(u32);"),
+            "unknown `clone` value, expected one of \"clone\", \"new\", \"reproduce\""
+        );
+        assert_eq!(
+            error("#[numid(overflow = \"wrap\", overflow = \"panic\")] struct Id(u32);"),
+            "duplicate numid option `overflow`"
+        );
+        assert_eq!(
+            error("#[numid(thread_cached = 0)] struct Id(u32);"),
+            "`thread_cached` must be positive"
+        );
+        assert_eq!(
+            error("#[numid(step = 0u8)] struct Id(u8);"),
+            "`step` must be positive"
+        );
+        assert!(error("struct Id { value: u32 }").contains("tuple struct"));
+        assert!(error("struct Id(u32, u32);").contains("tuple struct"));
+        assert!(error("enum Id { A }").contains("tuple struct"));
+        assert!(error("struct Id<T>(u32, T);").contains("generic"));
+    }
+}
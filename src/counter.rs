@@ -0,0 +1,255 @@
+//! The thread-safe "current value" storage of the ids generated by `numid!`.
+
+use core::cell::Cell;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::error::Error;
+use crate::primitive::{AtomicPrimitive, Primitive};
+use crate::source::IdSource;
+
+/// What happens when a new id is requested and the "current value" is already `MAX_VALUE`.
+///
+/// The policy is chosen with a built-in attribute of `numid!`
+/// (`@OverflowPanic`, `@OverflowSaturate` or `@OverflowWrap`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Overflow {
+    /// `new()` panics, `try_new()` return `Err(Error::Exhausted)`. This is the default policy.
+    Panic,
+    /// The "current value" is set to `MAX_VALUE` and stays there : `new()` panics and `try_new()`
+    /// return `Err(Error::Exhausted)`.
+    Saturate,
+    /// The "current value" wraps back to `INITIAL_VALUE` and the epoch is incremented :
+    /// the next id has value `INITIAL_VALUE + STEP` (ids are unique only inside an epoch).
+    /// If `INITIAL_VALUE + STEP` is above `MAX_VALUE`, `try_new()` return `Err(Error::Exhausted)`.
+    Wrap,
+}
+
+/// The state of a [`Counter`](struct.Counter.html) : its "current value" and its epoch,
+/// returned by the `snapshot()` method generated by `numid!`.
+///
+/// A snapshot saved before a restart can be given to `restore(snapshot)`, so the ids created
+/// after the restart don't reuse the values of the ids created before.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CounterSnapshot<T: Primitive> {
+    /// The "current value", the value of the last id created.
+    pub current_value: T,
+    /// The number of times the counter wrapped with `Overflow::Wrap`.
+    pub epoch: usize,
+}
+
+/// The "current value" of an id type, shared by all the threads.
+///
+/// Every type generated by `numid!` owns a `static` counter of this type.
+pub struct Counter<T: Primitive> {
+    value: T::Atomic,
+    epoch: AtomicUsize,
+}
+
+impl<T: Primitive> Counter<T> {
+    /// Create a counter from its storage, usable in a `static` :
+    ///
+    /// ```
+    /// use numid::{Counter, Primitive};
+    ///
+    /// static COUNTER: Counter<u32> = Counter::from_atomic(<<u32 as Primitive>::Atomic>::new(10));
+    /// assert_eq!(COUNTER.current_value(), 10);
+    /// ```
+    #[inline]
+    pub const fn from_atomic(value: T::Atomic) -> Counter<T> {
+        Counter {
+            value,
+            epoch: AtomicUsize::new(0),
+        }
+    }
+
+    /// Create a counter with `value` as "current value".
+    #[inline]
+    pub fn new(value: T) -> Counter<T> {
+        Counter::from_atomic(T::Atomic::new(value))
+    }
+
+    /// Increase the "current value" by `step` and return it.
+    ///
+    /// When the "current value" is above `T::MAX - step`, the behavior depends of `overflow` :
+    /// with `Overflow::Wrap` the counter restarts at `initial + step` and the epoch is incremented,
+    /// with `Overflow::Saturate` the counter is set to `T::MAX`, otherwise the counter is not
+    /// modified. `Err(Error::Exhausted)` is returned, unless the counter wrapped.
+    pub fn try_next(&self, overflow: Overflow, initial: T, step: T) -> Result<T, Error> {
+        let mut current = self.value.load();
+        loop {
+            let (next, wrapped) = match current.checked_add(step) {
+                Some(next) => (next, false),
+                None if overflow == Overflow::Wrap => match initial.checked_add(step) {
+                    Some(next) => (next, true),
+                    None => return Err(Error::Exhausted),
+                },
+                None if overflow == Overflow::Saturate && current != T::MAX => {
+                    match self.value.compare_exchange_weak(current, T::MAX) {
+                        Ok(_) => return Err(Error::Exhausted),
+                        Err(previous) => {
+                            current = previous;
+                            continue;
+                        }
+                    }
+                }
+                None => return Err(Error::Exhausted),
+            };
+            match self.value.compare_exchange_weak(current, next) {
+                Ok(_) => {
+                    if wrapped {
+                        self.epoch.fetch_add(1, Ordering::AcqRel);
+                    }
+                    return Ok(next);
+                }
+                Err(previous) => current = previous,
+            }
+        }
+    }
+
+    /// Increase the "current value" by `step` and return it, see [`Overflow`](enum.Overflow.html)
+    /// for the behavior when the counter is exhausted.
+    ///
+    /// # Panics
+    /// panic if the counter is exhausted and `overflow` is not `Overflow::Wrap`
+    #[inline]
+    pub fn next(&self, overflow: Overflow, initial: T, step: T) -> T {
+        __unwrap_next(self.try_next(overflow, initial, step))
+    }
+
+    /// Increase the "current value" by `count * step` and return the first value of the block
+    /// reserved, the block contains the `count` values following the previous "current value"
+    /// (separated by `step`).
+    ///
+    /// When the block doesn't fit below `T::MAX`, the behavior depends of `overflow` :
+    /// with `Overflow::Wrap` the block starts at `initial + step` and the epoch is incremented,
+    /// otherwise the counter is not modified and `Err(Error::Exhausted)` is returned.
+    pub fn try_reserve(
+        &self,
+        count: usize,
+        overflow: Overflow,
+        initial: T,
+        step: T,
+    ) -> Result<T, Error> {
+        let add = |start: T| {
+            let delta = (count as u128).checked_mul(step.to_bits())?;
+            // `T::MAX - start` is the number of values above `start`.
+            if delta <= T::MAX.wrapping_sub(start).to_bits() {
+                Some(start.wrapping_add(T::from_bits(delta)))
+            } else {
+                None
+            }
+        };
+
+        let mut current = self.value.load();
+        loop {
+            let (last, start, wrapped) = match add(current) {
+                Some(last) => (last, current, false),
+                None if overflow == Overflow::Wrap => match add(initial) {
+                    Some(last) => (last, initial, true),
+                    None => return Err(Error::Exhausted),
+                },
+                None => return Err(Error::Exhausted),
+            };
+            match self.value.compare_exchange_weak(current, last) {
+                Ok(_) => {
+                    if wrapped {
+                        self.epoch.fetch_add(1, Ordering::AcqRel);
+                    }
+                    return Ok(start.wrapping_add(step));
+                }
+                Err(previous) => current = previous,
+            }
+        }
+    }
+
+    /// Return the "current value".
+    #[inline]
+    pub fn current_value(&self) -> T {
+        self.value.load()
+    }
+
+    /// Return the number of times the counter wrapped with `Overflow::Wrap`.
+    #[inline]
+    pub fn epoch(&self) -> usize {
+        self.epoch.load(Ordering::Acquire)
+    }
+
+    /// Replace the "current value" by `value` if it superior.
+    /// Return true if the "current value" has been modified.
+    pub fn replace_current_value(&self, value: T) -> bool {
+        let mut current = self.value.load();
+        while value > current {
+            match self.value.compare_exchange_weak(current, value) {
+                Ok(_) => return true,
+                Err(previous) => current = previous,
+            }
+        }
+        false
+    }
+
+    /// Return the "current value" and the epoch of the counter.
+    #[inline]
+    pub fn snapshot(&self) -> CounterSnapshot<T> {
+        CounterSnapshot {
+            current_value: self.current_value(),
+            epoch: self.epoch(),
+        }
+    }
+
+    /// Move the counter forward to `snapshot` : the state is replaced if the epoch of `snapshot`
+    /// is greater, or if the epochs are equal and the "current value" of `snapshot` is superior.
+    /// Return true if the counter has been modified.
+    ///
+    /// The counter should not be used by other threads during the call.
+    pub fn restore(&self, snapshot: CounterSnapshot<T>) -> bool {
+        let epoch = self.epoch.fetch_max(snapshot.epoch, Ordering::AcqRel);
+        if snapshot.epoch < epoch {
+            return false;
+        }
+        if snapshot.epoch == epoch {
+            return self.replace_current_value(snapshot.current_value);
+        }
+        let mut current = self.value.load();
+        while let Err(previous) = self
+            .value
+            .compare_exchange_weak(current, snapshot.current_value)
+        {
+            current = previous;
+        }
+        true
+    }
+}
+
+/// Unwrap the result of `try_next`, panicking with the error.
+#[doc(hidden)]
+#[inline]
+pub fn __unwrap_next<T: Primitive>(next: Result<T, Error>) -> T {
+    match next {
+        Ok(value) => value,
+        Err(error) => panic!("{}", error),
+    }
+}
+
+/// Take the next value of a block cached by a thread, `cache` contains the next value of the block
+/// and the number of values left. A new block of `size` values is reserved from the source `counter` when
+/// the block is empty.
+#[doc(hidden)]
+pub fn __cached_next<T: Primitive, S: IdSource<T> + ?Sized>(
+    cache: &Cell<Option<(T, usize)>>,
+    counter: &S,
+    size: usize,
+    overflow: Overflow,
+    initial: T,
+    step: T,
+) -> Result<T, Error> {
+    let (value, left) = match cache.get() {
+        Some((value, left)) if left > 0 => (value, left),
+        _ => match counter.reserve_block(size, overflow, initial, step) {
+            Ok(first) => (first, size),
+            // Not enough values left for a whole block.
+            Err(_) => (counter.next(overflow, initial, step)?, 1),
+        },
+    };
+    cache.set(Some((value.wrapping_add(step), left - 1)));
+    Ok(value)
+}
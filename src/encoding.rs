@@ -0,0 +1,207 @@
+//! Compact string encodings of the ids generated by `numid!` : base62 and Crockford's base32.
+
+use core::fmt;
+use core::ops::Deref;
+
+use crate::parse::ParseError;
+use crate::primitive::Primitive;
+
+const BASE62: &[u8; 62] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+const CROCKFORD32: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// Number of digits of `u128::MAX` in base 32 (26) and in base 62 (22).
+const CAPACITY: usize = 26;
+
+/// An id encoded as a string, returned by `to_base62()` and `to_crockford32()`.
+///
+/// The string is stored inline (no allocation), it is accessible with [`as_str`](#method.as_str)
+/// or through `Deref<Target = str>`, and written as is by `Display`.
+#[derive(Clone, Copy)]
+pub struct EncodedId {
+    buf: [u8; CAPACITY],
+    start: u8,
+}
+
+impl EncodedId {
+    /// Return the encoded id as a string slice.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        // Only ASCII digits of the alphabets are written in `buf`.
+        core::str::from_utf8(&self.buf[self.start as usize..]).unwrap_or_default()
+    }
+}
+
+impl Deref for EncodedId {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for EncodedId {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl PartialEq for EncodedId {
+    fn eq(&self, other: &EncodedId) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for EncodedId {}
+
+impl PartialEq<str> for EncodedId {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for EncodedId {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl fmt::Display for EncodedId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+impl fmt::Debug for EncodedId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+/// Write the bits of `value` with the digits of `alphabet`.
+fn encode<T: Primitive>(value: T, alphabet: &[u8]) -> EncodedId {
+    let radix = alphabet.len() as u128;
+    let mut bits = value.to_bits();
+    let mut buf = [0; CAPACITY];
+    let mut start = CAPACITY;
+    loop {
+        start -= 1;
+        buf[start] = alphabet[(bits % radix) as usize];
+        bits /= radix;
+        if bits == 0 {
+            break;
+        }
+    }
+    EncodedId {
+        buf,
+        start: start as u8,
+    }
+}
+
+/// Read the bits of a value written with digits converted by `digit`, the characters for which
+/// `digit` return `Some(None)` are ignored.
+fn decode<T: Primitive>(
+    src: &str,
+    radix: u128,
+    digit: impl Fn(u8) -> Option<Option<u8>>,
+) -> Result<T, ParseError> {
+    let mut bits: u128 = 0;
+    let mut empty = true;
+    for byte in src.bytes() {
+        let d = match digit(byte).ok_or(ParseError::InvalidDigit)? {
+            Some(d) => d,
+            None => continue,
+        };
+        empty = false;
+        bits = bits
+            .checked_mul(radix)
+            .and_then(|bits| bits.checked_add(u128::from(d)))
+            .ok_or(ParseError::OutOfRange)?;
+    }
+    if empty {
+        return Err(ParseError::Empty);
+    }
+    if T::BITS < 128 && bits >> T::BITS != 0 {
+        return Err(ParseError::OutOfRange);
+    }
+    Ok(T::from_bits(bits))
+}
+
+/// Encode the bits of `value` (in two's complement for the signed types) in base62,
+/// with the digits `0-9`, `A-Z` and `a-z`.
+#[doc(hidden)]
+pub fn encode_base62<T: Primitive>(value: T) -> EncodedId {
+    encode(value, BASE62)
+}
+
+/// Decode a value encoded with `encode_base62`, the decoding is case-sensitive.
+#[doc(hidden)]
+pub fn decode_base62<T: Primitive>(src: &str) -> Result<T, ParseError> {
+    decode(src, 62, |byte| match byte {
+        b'0'..=b'9' => Some(Some(byte - b'0')),
+        b'A'..=b'Z' => Some(Some(byte - b'A' + 10)),
+        b'a'..=b'z' => Some(Some(byte - b'a' + 36)),
+        _ => None,
+    })
+}
+
+/// Encode the bits of `value` (in two's complement for the signed types) in Crockford's base32.
+#[doc(hidden)]
+pub fn encode_crockford32<T: Primitive>(value: T) -> EncodedId {
+    encode(value, CROCKFORD32)
+}
+
+/// Decode a value encoded in Crockford's base32 : the decoding is case-insensitive,
+/// `I` and `L` are read as `1`, `O` as `0` and the hyphens are ignored.
+#[doc(hidden)]
+pub fn decode_crockford32<T: Primitive>(src: &str) -> Result<T, ParseError> {
+    decode(src, 32, |byte| match byte.to_ascii_uppercase() {
+        b'-' => Some(None),
+        b'O' => Some(Some(0)),
+        b'I' | b'L' => Some(Some(1)),
+        upper => CROCKFORD32
+            .iter()
+            .position(|&c| c == upper)
+            .map(|d| Some(d as u8)),
+    })
+}
+
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __encoding_numid {
+    ($name:ident($ty:ty)) => {
+        impl $name {
+            /// Return the value of the id encoded in base62 (digits `0-9`, `A-Z` and `a-z`),
+            /// the signed values are encoded in two's complement.
+            #[allow(dead_code)]
+            #[inline]
+            pub fn to_base62(self) -> $crate::EncodedId {
+                $crate::__encode_base62(self.0)
+            }
+
+            /// Decode an id encoded with [`to_base62`](#method.to_base62), the values accepted are
+            /// the same as with `FromStr`. The "current value" is not modified, even with `@ParseCreate`.
+            #[allow(dead_code)]
+            pub fn from_base62(src: &str) -> Result<$name, $crate::ParseError> {
+                $name::__from_decoded($crate::__decode_base62(src)?)
+            }
+
+            /// Return the value of the id encoded in Crockford's base32, the signed values are
+            /// encoded in two's complement.
+            #[allow(dead_code)]
+            #[inline]
+            pub fn to_crockford32(self) -> $crate::EncodedId {
+                $crate::__encode_crockford32(self.0)
+            }
+
+            /// Decode an id encoded with [`to_crockford32`](#method.to_crockford32), the decoding
+            /// is case-insensitive, `I` and `L` are read as `1`, `O` as `0` and the hyphens are ignored.
+            /// The values accepted are the same as with `FromStr`, the "current value" is not modified.
+            #[allow(dead_code)]
+            pub fn from_crockford32(src: &str) -> Result<$name, $crate::ParseError> {
+                $name::__from_decoded($crate::__decode_crockford32(src)?)
+            }
+        }
+    };
+}
@@ -0,0 +1,41 @@
+//! Errors returned by the fallible methods of the ids generated by `numid!`.
+
+use core::fmt;
+
+/// The error type of the fallible methods generated by `numid!`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Error {
+    /// The value given to `try_create_lower` is greater than `INITIAL_VALUE`.
+    AboveInitialValue,
+    /// The value given to `try_create` is not greater than the "current value".
+    NotGreaterThanCurrent,
+    /// The "current value" reached `MAX_VALUE`, no new id can be created.
+    Exhausted,
+    /// No clock has been set for a snowflake id without the `std` feature, see `set_clock`.
+    NoClock,
+    /// The id source failed to provide a value, for example because its database is not reachable.
+    Unavailable,
+}
+
+/// Alias of [`Error`](enum.Error.html).
+pub type NumIdError = Error;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::AboveInitialValue => {
+                f.write_str("the value of the id is greater than `INITIAL_VALUE`")
+            }
+            Error::NotGreaterThanCurrent => {
+                f.write_str("the value of the id is not greater than the current value")
+            }
+            Error::Exhausted => f.write_str("the id space is exhausted"),
+            Error::NoClock => f.write_str("no clock has been set for the snowflake id"),
+            Error::Unavailable => f.write_str("the id source is unavailable"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
@@ -0,0 +1,230 @@
+//! Generational ids, generated with `numid!(@Generational ...)`, and their allocator.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use crate::error::Error;
+use crate::primitive::Primitive;
+
+/// An id made of an index and a generation, implemented by the `struct` generated
+/// with `numid!(@Generational ...)`.
+///
+/// The index can be reused once the id is freed, the generation distinguishes
+/// the successive ids sharing the same index.
+pub trait GenerationalId: Copy + Eq {
+    /// The integer type of the index and of the generation.
+    type Raw: Primitive;
+
+    /// Create an id from its index and generation.
+    fn from_parts(index: Self::Raw, generation: Self::Raw) -> Self;
+
+    /// Get the index of the id.
+    fn index(self) -> Self::Raw;
+
+    /// Get the generation of the id.
+    fn generation(self) -> Self::Raw;
+}
+
+#[cfg(feature = "alloc")]
+struct Slot<T> {
+    generation: T,
+    alive: bool,
+}
+
+/// An allocator of generational ids, reusing the indices of the freed ids
+/// with a bumped generation.
+///
+/// When the generation of an index reaches `MAX`, the index is retired instead of being reused,
+/// so a stale id is never considered alive again.
+///
+/// # Examples
+/// ```
+/// use numid::{numid, GenerationalAllocator};
+///
+/// numid!(@Generational pub struct EntityId(u32));
+///
+/// let mut allocator = GenerationalAllocator::new();
+/// let e0: EntityId = allocator.allocate();
+/// assert!(allocator.free(e0));
+///
+/// let e1 = allocator.allocate();
+/// assert_eq!(e1.index(), e0.index());
+/// assert_eq!(e1.generation(), 1);
+/// assert!(!allocator.is_alive(e0));
+/// assert!(allocator.is_alive(e1));
+/// ```
+#[cfg(feature = "alloc")]
+pub struct GenerationalAllocator<I: GenerationalId> {
+    slots: Vec<Slot<I::Raw>>,
+    free: Vec<usize>,
+    len: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl<I: GenerationalId> GenerationalAllocator<I> {
+    /// Create an empty allocator.
+    pub fn new() -> GenerationalAllocator<I> {
+        GenerationalAllocator {
+            slots: Vec::new(),
+            free: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// Return an id whose index is not used by an alive id, a freed index being reused first.
+    /// Return `Err(Error::Exhausted)` if all the indices are used or retired.
+    pub fn try_allocate(&mut self) -> Result<I, Error> {
+        let index = match self.free.pop() {
+            Some(index) => index,
+            None => {
+                let index = self.slots.len();
+                I::Raw::from_usize(index).ok_or(Error::Exhausted)?;
+                self.slots.push(Slot {
+                    generation: I::Raw::ZERO,
+                    alive: false,
+                });
+                index
+            }
+        };
+
+        let slot = &mut self.slots[index];
+        slot.alive = true;
+        self.len += 1;
+        // The index has been checked when the slot was created.
+        let raw_index = I::Raw::from_usize(index).unwrap();
+        Ok(I::from_parts(raw_index, slot.generation))
+    }
+
+    /// Same as [`try_allocate`](#method.try_allocate).
+    ///
+    /// # Panics
+    /// panic if all the indices are used or retired
+    pub fn allocate(&mut self) -> I {
+        match self.try_allocate() {
+            Ok(id) => id,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    /// Free the id, its index can be reused with a bumped generation.
+    /// Return false if the id is not alive.
+    pub fn free(&mut self, id: I) -> bool {
+        if !self.is_alive(id) {
+            return false;
+        }
+
+        // `is_alive` checked the index.
+        let index = id.index().to_usize().unwrap();
+        let slot = &mut self.slots[index];
+        slot.alive = false;
+        self.len -= 1;
+        if let Some(generation) = slot.generation.checked_add(I::Raw::ONE) {
+            slot.generation = generation;
+            self.free.push(index);
+        }
+        true
+    }
+
+    /// Return true if the id has been allocated and not freed.
+    pub fn is_alive(&self, id: I) -> bool {
+        match id.index().to_usize().and_then(|index| self.slots.get(index)) {
+            Some(slot) => slot.alive && slot.generation == id.generation(),
+            None => false,
+        }
+    }
+
+    /// Return the number of alive ids.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Return true if there is no alive id.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<I: GenerationalId> Default for GenerationalAllocator<I> {
+    fn default() -> GenerationalAllocator<I> {
+        GenerationalAllocator::new()
+    }
+}
+
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __generational_numid {
+    ($(#[$attr:meta])* $vis:vis struct $name:ident($ty:ty)) => {
+        /// A generational id generated with the `numid!` macro.
+        #[warn(non_camel_case_types)]
+        #[warn(dead_code)] // rust-lang : issue 66030
+        #[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+        $(#[$attr])*
+        $vis struct $name {
+            index: $ty,
+            generation: $ty,
+        }
+
+        impl $name {
+            /// Create an id from its index and generation.
+            #[allow(dead_code)]
+            #[inline]
+            pub const fn from_parts(index: $ty, generation: $ty) -> $name {
+                $name { index, generation }
+            }
+
+            /// Get the index of the id.
+            #[allow(dead_code)]
+            #[inline]
+            pub const fn index(self) -> $ty {
+                self.index
+            }
+
+            /// Get the generation of the id.
+            #[allow(dead_code)]
+            #[inline]
+            pub const fn generation(self) -> $ty {
+                self.generation
+            }
+        }
+
+        impl $crate::GenerationalId for $name {
+            type Raw = $ty;
+
+            #[inline]
+            fn from_parts(index: $ty, generation: $ty) -> $name {
+                $name::from_parts(index, generation)
+            }
+
+            #[inline]
+            fn index(self) -> $ty {
+                self.index
+            }
+
+            #[inline]
+            fn generation(self) -> $ty {
+                self.generation
+            }
+        }
+
+        $crate::__fmt_generational_numid!($name : Binary, Octal, LowerHex, UpperHex);
+        $crate::__display_numid!(__fmt_generational_numid $name);
+    };
+}
+
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __fmt_generational_numid {
+    ($name:ident : $($trait:ident),+) => {
+        $(
+            impl $crate::_core::fmt::$trait for $name {
+                fn fmt(&self, f: &mut $crate::_core::fmt::Formatter<'_>) -> $crate::_core::fmt::Result {
+                    $crate::_core::fmt::$trait::fmt(&self.index, f)?;
+                    f.write_str("v")?;
+                    $crate::_core::fmt::$trait::fmt(&self.generation, f)
+                }
+            }
+        )+
+    }
+}
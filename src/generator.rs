@@ -0,0 +1,158 @@
+//! Id generators owning their "current value", independent of the one used by `new()`.
+
+use alloc::sync::Arc;
+use core::fmt;
+use core::marker::PhantomData;
+
+use crate::counter::{Counter, CounterSnapshot};
+use crate::error::Error;
+use crate::num_id::NumId;
+use crate::range::IdRange;
+
+/// A generator of ids of type `I` with its own "current value".
+///
+/// The ids created by a generator don't modify the "current value" used by `I::new()`,
+/// and two generators don't share their "current value" : each generator is an independent
+/// id space. Cloning a generator doesn't create a new id space, the clones share the same
+/// "current value" and can be sent to other threads.
+///
+/// # Examples
+/// ```
+/// use numid::{numid, Generator};
+///
+/// numid!(struct MyId -> 10);
+///
+/// let document1 = Generator::<MyId>::new();
+/// let document2 = Generator::<MyId>::new();
+///
+/// assert_eq!(document1.next_id().value(), 11);
+/// assert_eq!(document1.next_id().value(), 12);
+/// assert_eq!(document2.next_id().value(), 11);
+/// assert_eq!(MyId::current_value(), 10);
+/// ```
+pub struct Generator<I: NumId> {
+    counter: Arc<Counter<I::Raw>>,
+    id: PhantomData<fn() -> I>,
+}
+
+impl<I: NumId> Generator<I> {
+    /// Create a generator with `INITIAL_VALUE` as "current value".
+    pub fn new() -> Generator<I> {
+        Generator::with_current_value(I::INITIAL_VALUE)
+    }
+
+    /// Create a generator with `value` as "current value",
+    /// the first id created will have value = `value + STEP`.
+    pub fn with_current_value(value: I::Raw) -> Generator<I> {
+        Generator {
+            counter: Arc::new(Counter::new(value)),
+            id: PhantomData,
+        }
+    }
+
+    /// Increase the "current value" of the generator by `STEP` and create a new id with
+    /// value = `current_value()`, see `I::new()`.
+    ///
+    /// # Panics
+    /// panic if the generator is exhausted and `I::OVERFLOW` is not `Overflow::Wrap`.
+    #[inline]
+    pub fn next_id(&self) -> I {
+        I::__from_raw(self.counter.next(I::OVERFLOW, I::INITIAL_VALUE, I::STEP))
+    }
+
+    /// Fallible version of [`next_id`](#method.next_id), see `I::try_new()`.
+    #[inline]
+    pub fn try_next_id(&self) -> Result<I, Error> {
+        self.counter
+            .try_next(I::OVERFLOW, I::INITIAL_VALUE, I::STEP)
+            .map(I::__from_raw)
+    }
+
+    /// Increase the "current value" of the generator by `count * STEP` and return the block
+    /// of the `count` new ids, see `I::reserve()`.
+    ///
+    /// # Panics
+    /// panic if the block doesn't fit below `MAX_VALUE` and `I::OVERFLOW` is not `Overflow::Wrap`.
+    pub fn reserve(&self, count: usize) -> IdRange<I> {
+        match self.try_reserve(count) {
+            Ok(range) => range,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    /// Fallible version of [`reserve`](#method.reserve), see `I::try_reserve()`.
+    pub fn try_reserve(&self, count: usize) -> Result<IdRange<I>, Error> {
+        self.counter
+            .try_reserve(count, I::OVERFLOW, I::INITIAL_VALUE, I::STEP)
+            .map(|first| IdRange::__new(first, count, I::STEP))
+    }
+
+    /// Return the "current value" of the generator.
+    #[inline]
+    pub fn current_value(&self) -> I::Raw {
+        self.counter.current_value()
+    }
+
+    /// Replace the "current value" of the generator by `value` if it superior.
+    /// Return true if the "current value" has been modified.
+    #[inline]
+    pub fn replace_current_value(&self, value: I::Raw) -> bool {
+        self.counter.replace_current_value(value)
+    }
+
+    /// Return the "current value" and the epoch of the generator.
+    #[inline]
+    pub fn snapshot(&self) -> CounterSnapshot<I::Raw> {
+        self.counter.snapshot()
+    }
+
+    /// Move the "current value" and the epoch of the generator forward to `snapshot`,
+    /// see `I::restore()`.
+    #[inline]
+    pub fn restore(&self, snapshot: CounterSnapshot<I::Raw>) -> bool {
+        self.counter.restore(snapshot)
+    }
+
+    /// Return Some id with specified value and replace the "current value" of the generator if
+    /// `replace_current_value(value)` is `true`.
+    /// Return None otherwise.
+    #[inline]
+    pub fn create_maybe(&self, value: I::Raw) -> Option<I> {
+        if self.replace_current_value(value) {
+            Some(I::__from_raw(value))
+        } else {
+            None
+        }
+    }
+
+    /// Return the number of times the "current value" of the generator wrapped back
+    /// to `INITIAL_VALUE`, always 0 unless `I::OVERFLOW` is `Overflow::Wrap`.
+    #[inline]
+    pub fn epoch(&self) -> usize {
+        self.counter.epoch()
+    }
+}
+
+impl<I: NumId> Clone for Generator<I> {
+    /// Return a generator sharing the same "current value".
+    fn clone(&self) -> Generator<I> {
+        Generator {
+            counter: Arc::clone(&self.counter),
+            id: PhantomData,
+        }
+    }
+}
+
+impl<I: NumId> Default for Generator<I> {
+    fn default() -> Generator<I> {
+        Generator::new()
+    }
+}
+
+impl<I: NumId> fmt::Debug for Generator<I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Generator")
+            .field("current_value", &self.current_value())
+            .finish()
+    }
+}
@@ -0,0 +1,178 @@
+//! Id generators reserving blocks of values durably in a file (the Hi/Lo algorithm).
+
+use std::fmt;
+#[cfg(unix)]
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+use crate::error::Error;
+use crate::lock;
+use crate::num_id::NumId;
+use crate::persist::{read_value, write_value};
+use crate::primitive::Primitive;
+
+struct State<T> {
+    current: T,
+    ceiling: T,
+}
+
+/// A generator of ids of type `I` which survives the restarts of the process : the values
+/// are reserved by blocks, the end of the block (the "ceiling") is written to a file before
+/// the first id of the block is created, and the ids of the block are then created in memory.
+///
+/// When a generator is opened, the ids restart after the ceiling recorded in the file : the ids
+/// of the last block not created before a crash are skipped, but an id is never created twice.
+///
+/// The "current value" of the generator is its own, and the overflow policy of `I` is not applied,
+/// see [Persistence](index.html#persistence).
+///
+/// On unix, the generator holds an exclusive `flock` of the file `path` + `.lock` (created next
+/// to the file and never removed) until it is dropped : opening a second generator of the same
+/// file, in this process or another, fails instead of creating the ids of the same block.
+///
+/// # Examples
+/// ```
+/// use numid::{numid, HiLoGenerator};
+///
+/// numid!(struct MyId);
+///
+/// # let dir = std::env::temp_dir().join(format!("numid-doc-hilo-{}", std::process::id()));
+/// # std::fs::create_dir_all(&dir).unwrap();
+/// let path = dir.join("my_id");
+/// let generator = HiLoGenerator::<MyId>::open(&path, 1000).unwrap();
+/// assert_eq!(generator.next_id().unwrap().value(), 1);
+/// assert_eq!(generator.next_id().unwrap().value(), 2);
+/// assert_eq!(std::fs::read_to_string(&path).unwrap(), "1000\n");
+///
+/// // After a restart.
+/// drop(generator);
+/// let generator = HiLoGenerator::<MyId>::open(&path, 1000).unwrap();
+/// assert_eq!(generator.next_id().unwrap().value(), 1001);
+/// # std::fs::remove_dir_all(&dir).unwrap();
+/// ```
+pub struct HiLoGenerator<I: NumId> {
+    path: PathBuf,
+    block: usize,
+    state: Mutex<State<I::Raw>>,
+    // Locked until the generator is dropped.
+    #[cfg(unix)]
+    _lock: File,
+    id: PhantomData<fn() -> I>,
+}
+
+impl<I: NumId> HiLoGenerator<I> {
+    /// Open a generator reserving blocks of `block` ids in the file `path`, the first id created
+    /// follows the ceiling recorded in the file, or `INITIAL_VALUE` if the file doesn't exist.
+    ///
+    /// The error kind is `InvalidData` if the file doesn't contain a value of type `I::Raw`, and
+    /// `WouldBlock` if the file is used by another generator.
+    ///
+    /// # Panics
+    /// panic if `block` is 0
+    pub fn open(path: impl AsRef<Path>, block: usize) -> io::Result<HiLoGenerator<I>> {
+        assert!(block != 0, "block size must be non-zero");
+        let path = path.as_ref().to_path_buf();
+        #[cfg(unix)]
+        let lock = {
+            let mut lock_path = path.clone().into_os_string();
+            lock_path.push(".lock");
+            let file = OpenOptions::new()
+                .write(true)
+                .create(true)
+                .truncate(false)
+                .open(lock_path)?;
+            lock::try_lock(&file).map_err(|error| {
+                if error.kind() == io::ErrorKind::WouldBlock {
+                    io::Error::new(error.kind(), "the file is used by another generator")
+                } else {
+                    error
+                }
+            })?;
+            file
+        };
+        let ceiling = match read_value(&path)? {
+            Some(ceiling) if ceiling > I::INITIAL_VALUE => ceiling,
+            _ => I::INITIAL_VALUE,
+        };
+        Ok(HiLoGenerator {
+            path,
+            block,
+            state: Mutex::new(State {
+                current: ceiling,
+                ceiling,
+            }),
+            #[cfg(unix)]
+            _lock: lock,
+            id: PhantomData,
+        })
+    }
+
+    fn state(&self) -> MutexGuard<'_, State<I::Raw>> {
+        lock::lock(&self.state)
+    }
+
+    /// Increase the "current value" of the generator by `STEP` and create a new id with
+    /// value = `current_value()`. A new ceiling is written to the file when the block is
+    /// exhausted.
+    ///
+    /// Return an error if the file can't be written, or an error of kind `Other` wrapping
+    /// `Error::Exhausted` if the "current value" is `MAX_VALUE`.
+    pub fn next_id(&self) -> io::Result<I> {
+        let mut state = self.state();
+        let next = match state.current.checked_add(I::STEP) {
+            Some(next) => next,
+            None => return Err(io::Error::other(Error::Exhausted)),
+        };
+        if next > state.ceiling {
+            let step = I::STEP.to_bits();
+            let room = I::MAX_VALUE.wrapping_sub(state.current).to_bits();
+            let delta = (self.block as u128)
+                .checked_mul(step)
+                .map_or(room, |delta| delta.min(room));
+            let ceiling = state
+                .current
+                .wrapping_add(I::Raw::from_bits(delta - delta % step));
+            write_value(&self.path, ceiling)?;
+            state.ceiling = ceiling;
+        }
+        state.current = next;
+        Ok(I::__from_raw(next))
+    }
+
+    /// Return the "current value" of the generator.
+    pub fn current_value(&self) -> I::Raw {
+        self.state().current
+    }
+
+    /// Return the end of the block of ids reserved, the value recorded in the file.
+    pub fn ceiling(&self) -> I::Raw {
+        self.state().ceiling
+    }
+
+    /// Return the number of ids of a block.
+    #[inline]
+    pub fn block(&self) -> usize {
+        self.block
+    }
+
+    /// Return the path of the file recording the ceiling.
+    #[inline]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl<I: NumId> fmt::Debug for HiLoGenerator<I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.state();
+        f.debug_struct("HiLoGenerator")
+            .field("path", &self.path)
+            .field("block", &self.block)
+            .field("current_value", &state.current)
+            .field("ceiling", &state.ceiling)
+            .finish()
+    }
+}
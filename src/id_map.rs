@@ -0,0 +1,503 @@
+//! A map keyed by ids, stored in a vector indexed by the values of the ids.
+
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt;
+use core::iter::{FromIterator, FusedIterator};
+use core::marker::PhantomData;
+use core::ops::{Index, IndexMut};
+
+use crate::num_id::NumId;
+use crate::primitive::Primitive;
+
+/// Return the index of `key` : `(value - INITIAL_VALUE) / STEP`, `None` if the value is below
+/// `INITIAL_VALUE` or not a multiple of `STEP` away from it.
+#[inline]
+pub(crate) fn key_index<K: NumId>(key: K) -> Option<usize> {
+    if key.value() < K::INITIAL_VALUE {
+        return None;
+    }
+    let offset = key.value().wrapping_sub(K::INITIAL_VALUE).to_bits();
+    let step = K::STEP.to_bits();
+    if offset % step != 0 {
+        return None;
+    }
+    usize::try_from(offset / step).ok()
+}
+
+/// The inverse of `key_index`.
+#[inline]
+pub(crate) fn index_key<K: NumId>(index: usize) -> K {
+    let offset = (index as u128).wrapping_mul(K::STEP.to_bits());
+    K::__from_raw(K::INITIAL_VALUE.wrapping_add(K::Raw::from_bits(offset)))
+}
+
+/// A map from ids of type `K` to values of type `V`, stored in a `Vec<Option<V>>` indexed by
+/// `(id.value() - INITIAL_VALUE) / STEP`.
+///
+/// The ids created with `new()` are dense, so the map uses much less memory and is much faster
+/// than a `HashMap` for them. The memory used is proportional to the greatest id inserted.
+/// The ids below `INITIAL_VALUE` (created with `create_lower`) can't be inserted.
+///
+/// # Examples
+/// ```
+/// use numid::{numid, IdMap};
+///
+/// numid!(struct MyId -> 10);
+///
+/// let (a, b) = (MyId::new(), MyId::new());
+/// let mut map = IdMap::new();
+/// map.insert(b, "b");
+/// map.insert(a, "a");
+/// *map.entry(MyId::new()).or_insert("c") = "C";
+///
+/// assert_eq!(map[a], "a");
+/// assert_eq!(map.values().copied().collect::<Vec<_>>(), vec!["a", "b", "C"]);
+/// ```
+pub struct IdMap<K: NumId, V> {
+    slots: Vec<Option<V>>,
+    len: usize,
+    key: PhantomData<fn(K) -> K>,
+}
+
+impl<K: NumId, V> IdMap<K, V> {
+    /// Create an empty map.
+    #[inline]
+    pub fn new() -> IdMap<K, V> {
+        IdMap::with_capacity(0)
+    }
+
+    /// Create an empty map with room for the ids up to `INITIAL_VALUE + capacity * STEP`.
+    pub fn with_capacity(capacity: usize) -> IdMap<K, V> {
+        IdMap {
+            slots: Vec::with_capacity(capacity),
+            len: 0,
+            key: PhantomData,
+        }
+    }
+
+    /// Return the number of entries of the map.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Return true if the map contains no entry.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Remove all the entries of the map.
+    pub fn clear(&mut self) {
+        self.slots.clear();
+        self.len = 0;
+    }
+
+    /// Return true if the map contains an entry for `key`.
+    #[inline]
+    pub fn contains_key(&self, key: K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Return a reference to the value of `key`.
+    #[inline]
+    pub fn get(&self, key: K) -> Option<&V> {
+        self.slots.get(key_index(key)?)?.as_ref()
+    }
+
+    /// Return a mutable reference to the value of `key`.
+    #[inline]
+    pub fn get_mut(&mut self, key: K) -> Option<&mut V> {
+        self.slots.get_mut(key_index(key)?)?.as_mut()
+    }
+
+    /// Insert a value for `key`, return the previous value of `key`.
+    ///
+    /// # Panics
+    /// panic if `key` is below `INITIAL_VALUE` or is not a multiple of `STEP` away from it
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let previous = self.slot(key).replace(value);
+        if previous.is_none() {
+            self.len += 1;
+        }
+        previous
+    }
+
+    /// Remove the entry of `key` and return its value.
+    pub fn remove(&mut self, key: K) -> Option<V> {
+        let value = self.slots.get_mut(key_index(key)?)?.take();
+        if value.is_some() {
+            self.len -= 1;
+        }
+        value
+    }
+
+    /// Return the entry of `key` for in-place manipulation.
+    ///
+    /// # Panics
+    /// panic if `key` is below `INITIAL_VALUE` or is not a multiple of `STEP` away from it
+    pub fn entry(&mut self, key: K) -> IdMapEntry<'_, K, V> {
+        let index = index_or_panic(key);
+        if index >= self.slots.len() || self.slots[index].is_none() {
+            IdMapEntry::Vacant(IdMapVacantEntry { map: self, key })
+        } else {
+            IdMapEntry::Occupied(IdMapOccupiedEntry { map: self, key })
+        }
+    }
+
+    /// Return an iterator over the entries of the map, in the order of the ids.
+    pub fn iter(&self) -> IdMapIter<'_, K, V> {
+        IdMapIter {
+            slots: self.slots.iter().enumerate(),
+            len: self.len,
+            key: PhantomData,
+        }
+    }
+
+    /// Return an iterator over the entries of the map with mutable references to the values,
+    /// in the order of the ids.
+    pub fn iter_mut(&mut self) -> IdMapIterMut<'_, K, V> {
+        IdMapIterMut {
+            slots: self.slots.iter_mut().enumerate(),
+            len: self.len,
+            key: PhantomData,
+        }
+    }
+
+    /// Return an iterator over the ids of the map, in order.
+    pub fn keys(&self) -> impl Iterator<Item = K> + '_ {
+        self.iter().map(|(key, _)| key)
+    }
+
+    /// Return an iterator over the values of the map, in the order of the ids.
+    pub fn values(&self) -> impl Iterator<Item = &V> + '_ {
+        self.iter().map(|(_, value)| value)
+    }
+
+    /// Return an iterator over mutable references to the values of the map, in the order of the ids.
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut V> + '_ {
+        self.iter_mut().map(|(_, value)| value)
+    }
+
+    /// Return the slot of `key`, growing the vector if needed.
+    fn slot(&mut self, key: K) -> &mut Option<V> {
+        let index = index_or_panic(key);
+        if index >= self.slots.len() {
+            self.slots.resize_with(index + 1, || None);
+        }
+        &mut self.slots[index]
+    }
+}
+
+fn index_or_panic<K: NumId>(key: K) -> usize {
+    match key_index(key) {
+        Some(index) => index,
+        None => panic!("the id {:?} can't be used as a key of an `IdMap`", key),
+    }
+}
+
+impl<K: NumId, V> Default for IdMap<K, V> {
+    #[inline]
+    fn default() -> IdMap<K, V> {
+        IdMap::new()
+    }
+}
+
+impl<K: NumId, V: Clone> Clone for IdMap<K, V> {
+    fn clone(&self) -> IdMap<K, V> {
+        IdMap {
+            slots: self.slots.clone(),
+            len: self.len,
+            key: PhantomData,
+        }
+    }
+}
+
+impl<K: NumId, V: fmt::Debug> fmt::Debug for IdMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: NumId, V: PartialEq> PartialEq for IdMap<K, V> {
+    fn eq(&self, other: &IdMap<K, V>) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<K: NumId, V: Eq> Eq for IdMap<K, V> {}
+
+/// # Panics
+/// panic if the map contains no entry for the id
+impl<K: NumId, V> Index<K> for IdMap<K, V> {
+    type Output = V;
+
+    #[inline]
+    fn index(&self, key: K) -> &V {
+        match self.get(key) {
+            Some(value) => value,
+            None => panic!("no entry for the id {:?}", key),
+        }
+    }
+}
+
+/// # Panics
+/// panic if the map contains no entry for the id
+impl<K: NumId, V> IndexMut<K> for IdMap<K, V> {
+    #[inline]
+    fn index_mut(&mut self, key: K) -> &mut V {
+        match self.get_mut(key) {
+            Some(value) => value,
+            None => panic!("no entry for the id {:?}", key),
+        }
+    }
+}
+
+impl<K: NumId, V> FromIterator<(K, V)> for IdMap<K, V> {
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> IdMap<K, V> {
+        let mut map = IdMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K: NumId, V> Extend<(K, V)> for IdMap<K, V> {
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<'a, K: NumId, V> IntoIterator for &'a IdMap<K, V> {
+    type Item = (K, &'a V);
+    type IntoIter = IdMapIter<'a, K, V>;
+
+    #[inline]
+    fn into_iter(self) -> IdMapIter<'a, K, V> {
+        self.iter()
+    }
+}
+
+impl<'a, K: NumId, V> IntoIterator for &'a mut IdMap<K, V> {
+    type Item = (K, &'a mut V);
+    type IntoIter = IdMapIterMut<'a, K, V>;
+
+    #[inline]
+    fn into_iter(self) -> IdMapIterMut<'a, K, V> {
+        self.iter_mut()
+    }
+}
+
+/// An entry of an [`IdMap`](struct.IdMap.html), returned by [`IdMap::entry`](struct.IdMap.html#method.entry).
+pub enum IdMapEntry<'a, K: NumId, V> {
+    /// An entry with a value.
+    Occupied(IdMapOccupiedEntry<'a, K, V>),
+    /// An entry without value.
+    Vacant(IdMapVacantEntry<'a, K, V>),
+}
+
+impl<'a, K: NumId, V> IdMapEntry<'a, K, V> {
+    /// Return the id of the entry.
+    #[inline]
+    pub fn key(&self) -> K {
+        match self {
+            IdMapEntry::Occupied(entry) => entry.key,
+            IdMapEntry::Vacant(entry) => entry.key,
+        }
+    }
+
+    /// Insert `default` if the entry is vacant, and return a mutable reference to the value.
+    #[inline]
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        self.or_insert_with(|| default)
+    }
+
+    /// Insert the result of `default` if the entry is vacant, and return a mutable reference
+    /// to the value.
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            IdMapEntry::Occupied(entry) => entry.into_mut(),
+            IdMapEntry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    /// Call `f` with the value if the entry is occupied.
+    pub fn and_modify<F: FnOnce(&mut V)>(mut self, f: F) -> IdMapEntry<'a, K, V> {
+        if let IdMapEntry::Occupied(entry) = &mut self {
+            f(entry.get_mut());
+        }
+        self
+    }
+}
+
+impl<'a, K: NumId, V: Default> IdMapEntry<'a, K, V> {
+    /// Insert `V::default()` if the entry is vacant, and return a mutable reference to the value.
+    #[inline]
+    pub fn or_default(self) -> &'a mut V {
+        self.or_insert_with(V::default)
+    }
+}
+
+/// An entry of an [`IdMap`](struct.IdMap.html) with a value.
+pub struct IdMapOccupiedEntry<'a, K: NumId, V> {
+    map: &'a mut IdMap<K, V>,
+    key: K,
+}
+
+impl<'a, K: NumId, V> IdMapOccupiedEntry<'a, K, V> {
+    /// Return the id of the entry.
+    #[inline]
+    pub fn key(&self) -> K {
+        self.key
+    }
+
+    /// Return a reference to the value.
+    #[inline]
+    pub fn get(&self) -> &V {
+        &self.map[self.key]
+    }
+
+    /// Return a mutable reference to the value.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut V {
+        &mut self.map[self.key]
+    }
+
+    /// Return a mutable reference to the value, with the lifetime of the map.
+    #[inline]
+    pub fn into_mut(self) -> &'a mut V {
+        &mut self.map[self.key]
+    }
+
+    /// Replace the value and return the previous one.
+    #[inline]
+    pub fn insert(&mut self, value: V) -> V {
+        core::mem::replace(self.get_mut(), value)
+    }
+
+    /// Remove the entry from the map and return its value.
+    #[inline]
+    pub fn remove(self) -> V {
+        match self.map.remove(self.key) {
+            Some(value) => value,
+            None => unreachable!(),
+        }
+    }
+}
+
+/// An entry of an [`IdMap`](struct.IdMap.html) without value.
+pub struct IdMapVacantEntry<'a, K: NumId, V> {
+    map: &'a mut IdMap<K, V>,
+    key: K,
+}
+
+impl<'a, K: NumId, V> IdMapVacantEntry<'a, K, V> {
+    /// Return the id of the entry.
+    #[inline]
+    pub fn key(&self) -> K {
+        self.key
+    }
+
+    /// Insert a value in the entry and return a mutable reference to it.
+    pub fn insert(self, value: V) -> &'a mut V {
+        self.map.len += 1;
+        self.map.slot(self.key).insert(value)
+    }
+}
+
+/// An iterator over the entries of an [`IdMap`](struct.IdMap.html), in the order of the ids.
+pub struct IdMapIter<'a, K: NumId, V> {
+    slots: core::iter::Enumerate<core::slice::Iter<'a, Option<V>>>,
+    len: usize,
+    key: PhantomData<fn() -> K>,
+}
+
+impl<'a, K: NumId, V> Iterator for IdMapIter<'a, K, V> {
+    type Item = (K, &'a V);
+
+    fn next(&mut self) -> Option<(K, &'a V)> {
+        for (index, slot) in &mut self.slots {
+            if let Some(value) = slot {
+                self.len -= 1;
+                return Some((index_key(index), value));
+            }
+        }
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, K: NumId, V> DoubleEndedIterator for IdMapIter<'a, K, V> {
+    fn next_back(&mut self) -> Option<(K, &'a V)> {
+        while let Some((index, slot)) = self.slots.next_back() {
+            if let Some(value) = slot {
+                self.len -= 1;
+                return Some((index_key(index), value));
+            }
+        }
+        None
+    }
+}
+
+impl<K: NumId, V> ExactSizeIterator for IdMapIter<'_, K, V> {}
+
+impl<K: NumId, V> FusedIterator for IdMapIter<'_, K, V> {}
+
+impl<K: NumId, V> Clone for IdMapIter<'_, K, V> {
+    fn clone(&self) -> Self {
+        IdMapIter {
+            slots: self.slots.clone(),
+            len: self.len,
+            key: PhantomData,
+        }
+    }
+}
+
+/// An iterator over the entries of an [`IdMap`](struct.IdMap.html) with mutable references
+/// to the values, in the order of the ids.
+pub struct IdMapIterMut<'a, K: NumId, V> {
+    slots: core::iter::Enumerate<core::slice::IterMut<'a, Option<V>>>,
+    len: usize,
+    key: PhantomData<fn() -> K>,
+}
+
+impl<'a, K: NumId, V> Iterator for IdMapIterMut<'a, K, V> {
+    type Item = (K, &'a mut V);
+
+    fn next(&mut self) -> Option<(K, &'a mut V)> {
+        for (index, slot) in &mut self.slots {
+            if let Some(value) = slot {
+                self.len -= 1;
+                return Some((index_key(index), value));
+            }
+        }
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, K: NumId, V> DoubleEndedIterator for IdMapIterMut<'a, K, V> {
+    fn next_back(&mut self) -> Option<(K, &'a mut V)> {
+        while let Some((index, slot)) = self.slots.next_back() {
+            if let Some(value) = slot {
+                self.len -= 1;
+                return Some((index_key(index), value));
+            }
+        }
+        None
+    }
+}
+
+impl<K: NumId, V> ExactSizeIterator for IdMapIterMut<'_, K, V> {}
+
+impl<K: NumId, V> FusedIterator for IdMapIterMut<'_, K, V> {}
@@ -0,0 +1,809 @@
+//! A compressed set of ids, a bitmap split in chunks of 2^16 values stored as sorted arrays
+//! when they are sparse and as bitsets when they are dense (like the roaring bitmaps).
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt;
+use core::iter::{FromIterator, FusedIterator};
+use core::marker::PhantomData;
+use core::ops::{BitAnd, BitOr, Sub};
+
+use crate::num_id::NumId;
+use crate::primitive::Primitive;
+
+/// The maximal length of an array chunk, the denser chunks are stored as bitsets.
+const ARRAY_MAX: usize = 4096;
+
+/// Number of `u64` words of a bitset chunk.
+const WORDS: usize = 1024;
+
+const ARRAY: u8 = 0;
+const BITSET: u8 = 1;
+const FORMAT_VERSION: u8 = 1;
+
+/// The values of a chunk, the 16 low bits of the ids.
+#[derive(Clone, PartialEq, Eq)]
+enum Chunk {
+    /// Sorted values, at most `ARRAY_MAX`.
+    Array(Vec<u16>),
+    /// A bitset and its number of bits set, more than `ARRAY_MAX`.
+    Bitset(Box<[u64; WORDS]>, usize),
+}
+
+impl Chunk {
+    fn len(&self) -> usize {
+        match self {
+            Chunk::Array(values) => values.len(),
+            Chunk::Bitset(_, len) => *len,
+        }
+    }
+
+    fn contains(&self, low: u16) -> bool {
+        match self {
+            Chunk::Array(values) => values.binary_search(&low).is_ok(),
+            Chunk::Bitset(words, _) => words[low as usize / 64] & 1 << (low % 64) != 0,
+        }
+    }
+
+    /// Insert a value, return true if it was not present.
+    fn insert(&mut self, low: u16) -> bool {
+        match self {
+            Chunk::Array(values) => match values.binary_search(&low) {
+                Ok(_) => false,
+                Err(index) => {
+                    values.insert(index, low);
+                    if values.len() > ARRAY_MAX {
+                        *self = Chunk::from_bitset(self.to_bitset());
+                    }
+                    true
+                }
+            },
+            Chunk::Bitset(words, len) => {
+                let (word, bit) = (&mut words[low as usize / 64], 1 << (low % 64));
+                let inserted = *word & bit == 0;
+                *word |= bit;
+                *len += usize::from(inserted);
+                inserted
+            }
+        }
+    }
+
+    /// Remove a value, return true if it was present.
+    fn remove(&mut self, low: u16) -> bool {
+        match self {
+            Chunk::Array(values) => match values.binary_search(&low) {
+                Ok(index) => {
+                    values.remove(index);
+                    true
+                }
+                Err(_) => false,
+            },
+            Chunk::Bitset(words, len) => {
+                let (word, bit) = (&mut words[low as usize / 64], 1 << (low % 64));
+                let removed = *word & bit != 0;
+                *word &= !bit;
+                *len -= usize::from(removed);
+                if *len <= ARRAY_MAX {
+                    *self = Chunk::from_bitset(self.to_bitset());
+                }
+                removed
+            }
+        }
+    }
+
+    /// Return the number of values lower than or equal to `low`.
+    fn rank(&self, low: u16) -> usize {
+        match self {
+            Chunk::Array(values) => match values.binary_search(&low) {
+                Ok(index) => index + 1,
+                Err(index) => index,
+            },
+            Chunk::Bitset(words, _) => {
+                let (index, bit) = (low as usize / 64, low % 64);
+                let below: u32 = words[..index].iter().map(|word| word.count_ones()).sum();
+                let mask = u64::MAX >> (63 - bit);
+                (below + (words[index] & mask).count_ones()) as usize
+            }
+        }
+    }
+
+    /// Return the `n`-th value (from 0), `n` must be lower than `len()`.
+    fn select(&self, mut n: usize) -> u16 {
+        match self {
+            Chunk::Array(values) => values[n],
+            Chunk::Bitset(words, _) => {
+                for (index, &word) in words.iter().enumerate() {
+                    let count = word.count_ones() as usize;
+                    if n < count {
+                        let mut word = word;
+                        for _ in 0..n {
+                            word &= word - 1;
+                        }
+                        return (index * 64) as u16 + word.trailing_zeros() as u16;
+                    }
+                    n -= count;
+                }
+                unreachable!()
+            }
+        }
+    }
+
+    fn to_bitset(&self) -> Box<[u64; WORDS]> {
+        match self {
+            Chunk::Array(values) => {
+                let mut words = Box::new([0; WORDS]);
+                for &low in values {
+                    words[low as usize / 64] |= 1 << (low % 64);
+                }
+                words
+            }
+            Chunk::Bitset(words, _) => words.clone(),
+        }
+    }
+
+    /// Create a chunk from a bitset, as an array if it is not dense.
+    fn from_bitset(words: Box<[u64; WORDS]>) -> Chunk {
+        let len = words.iter().map(|word| word.count_ones() as usize).sum();
+        if len > ARRAY_MAX {
+            Chunk::Bitset(words, len)
+        } else {
+            Chunk::Array(ChunkIter::new(&Chunk::Bitset(words, len)).collect())
+        }
+    }
+
+    /// Combine two chunks with a set operation.
+    fn combine(&self, other: &Chunk, op: Operation) -> Chunk {
+        match (self, other) {
+            (Chunk::Array(a), Chunk::Array(b)) => {
+                let values = merge(a, b, op);
+                if values.len() > ARRAY_MAX {
+                    Chunk::from_bitset(Chunk::Array(values).to_bitset())
+                } else {
+                    Chunk::Array(values)
+                }
+            }
+            (Chunk::Array(a), b) if op != Operation::Union => Chunk::Array(
+                a.iter()
+                    .copied()
+                    .filter(|&low| b.contains(low) == (op == Operation::Intersection))
+                    .collect(),
+            ),
+            _ => {
+                let mut words = self.to_bitset();
+                let other = other.to_bitset();
+                for (word, other) in words.iter_mut().zip(other.iter()) {
+                    *word = match op {
+                        Operation::Union => *word | other,
+                        Operation::Intersection => *word & other,
+                        Operation::Difference => *word & !other,
+                    };
+                }
+                Chunk::from_bitset(words)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Operation {
+    Union,
+    Intersection,
+    Difference,
+}
+
+/// Merge two sorted arrays with a set operation.
+fn merge(a: &[u16], b: &[u16], op: Operation) -> Vec<u16> {
+    let mut values = Vec::with_capacity(match op {
+        Operation::Union => a.len() + b.len(),
+        Operation::Intersection => a.len().min(b.len()),
+        Operation::Difference => a.len(),
+    });
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            Ordering::Less => {
+                if op != Operation::Intersection {
+                    values.push(a[i]);
+                }
+                i += 1;
+            }
+            Ordering::Greater => {
+                if op == Operation::Union {
+                    values.push(b[j]);
+                }
+                j += 1;
+            }
+            Ordering::Equal => {
+                if op != Operation::Difference {
+                    values.push(a[i]);
+                }
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    if op != Operation::Intersection {
+        values.extend_from_slice(&a[i..]);
+    }
+    if op == Operation::Union {
+        values.extend_from_slice(&b[j..]);
+    }
+    values
+}
+
+/// Return the bits of `key` with the sign bit flipped for the signed types,
+/// so the bits are in the same order as the ids.
+#[inline]
+fn ordered_bits<K: NumId>(key: K) -> u128 {
+    key.value().to_bits() ^ sign_bit::<K>()
+}
+
+#[inline]
+fn sign_bit<K: NumId>() -> u128 {
+    let max = K::Raw::MAX.to_bits();
+    if max.count_ones() < K::Raw::BITS {
+        max + 1
+    } else {
+        0
+    }
+}
+
+#[inline]
+fn split<K: NumId>(key: K) -> (u128, u16) {
+    let bits = ordered_bits(key);
+    (bits >> 16, bits as u16)
+}
+
+#[inline]
+fn join<K: NumId>(high: u128, low: u16) -> K {
+    let bits = (high << 16 | u128::from(low)) ^ sign_bit::<K>();
+    K::__from_raw(K::Raw::from_bits(bits))
+}
+
+/// A compressed set of ids, iterated in the order of the ids.
+///
+/// The values are grouped in chunks of 2^16 values, a chunk is stored as a sorted array of 16 bits
+/// values when it contains at most 4096 ids and as a bitset of 8 KiB otherwise (like the roaring
+/// bitmaps), so large sets of dense ids take about one bit per id.
+///
+/// The set can be written to a compact byte format with [`to_bytes`](#method.to_bytes)
+/// and read back with [`from_bytes`](#method.from_bytes).
+///
+/// # Examples
+/// ```
+/// use numid::{numid, IdSet};
+///
+/// numid!(struct MyId(u32));
+///
+/// let ids: Vec<MyId> = (0..10).map(|_| MyId::new()).collect();
+/// let even: IdSet<MyId> = ids.iter().copied().step_by(2).collect();
+/// let first: IdSet<MyId> = ids[..5].iter().copied().collect();
+///
+/// assert_eq!((&even & &first).len(), 3);
+/// assert_eq!((&even | &first).len(), 7);
+/// assert_eq!(even.rank(ids[4]), 3);
+/// assert_eq!(even.select(1), Some(ids[2]));
+/// assert_eq!(IdSet::from_bytes(&even.to_bytes()), Ok(even));
+/// ```
+pub struct IdSet<K: NumId> {
+    chunks: Vec<(u128, Chunk)>,
+    len: usize,
+    key: PhantomData<fn(K) -> K>,
+}
+
+impl<K: NumId> IdSet<K> {
+    /// Create an empty set.
+    #[inline]
+    pub fn new() -> IdSet<K> {
+        IdSet {
+            chunks: Vec::new(),
+            len: 0,
+            key: PhantomData,
+        }
+    }
+
+    /// Return the number of ids of the set.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Return true if the set contains no id.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Remove all the ids of the set.
+    pub fn clear(&mut self) {
+        self.chunks.clear();
+        self.len = 0;
+    }
+
+    fn find(&self, high: u128) -> Result<usize, usize> {
+        self.chunks.binary_search_by(|(other, _)| other.cmp(&high))
+    }
+
+    /// Return true if the set contains `key`.
+    pub fn contains(&self, key: K) -> bool {
+        let (high, low) = split(key);
+        match self.find(high) {
+            Ok(index) => self.chunks[index].1.contains(low),
+            Err(_) => false,
+        }
+    }
+
+    /// Add `key` to the set, return true if it was not present.
+    pub fn insert(&mut self, key: K) -> bool {
+        let (high, low) = split(key);
+        let inserted = match self.find(high) {
+            Ok(index) => self.chunks[index].1.insert(low),
+            Err(index) => {
+                self.chunks
+                    .insert(index, (high, Chunk::Array(alloc::vec![low])));
+                true
+            }
+        };
+        self.len += usize::from(inserted);
+        inserted
+    }
+
+    /// Remove `key` from the set, return true if it was present.
+    pub fn remove(&mut self, key: K) -> bool {
+        let (high, low) = split(key);
+        let index = match self.find(high) {
+            Ok(index) => index,
+            Err(_) => return false,
+        };
+        let removed = self.chunks[index].1.remove(low);
+        if self.chunks[index].1.len() == 0 {
+            self.chunks.remove(index);
+        }
+        self.len -= usize::from(removed);
+        removed
+    }
+
+    /// Return the number of ids of the set lower than or equal to `key`.
+    pub fn rank(&self, key: K) -> usize {
+        let (high, low) = split(key);
+        let index = match self.find(high) {
+            Ok(index) | Err(index) => index,
+        };
+        let below: usize = self.chunks[..index]
+            .iter()
+            .map(|(_, chunk)| chunk.len())
+            .sum();
+        match self.chunks.get(index) {
+            Some((other, chunk)) if *other == high => below + chunk.rank(low),
+            _ => below,
+        }
+    }
+
+    /// Return the `n`-th id of the set in order (from 0), `None` if `n >= len()`.
+    pub fn select(&self, mut n: usize) -> Option<K> {
+        for (high, chunk) in &self.chunks {
+            if n < chunk.len() {
+                return Some(join(*high, chunk.select(n)));
+            }
+            n -= chunk.len();
+        }
+        None
+    }
+
+    /// Return the lowest id of the set.
+    #[inline]
+    pub fn first(&self) -> Option<K> {
+        self.select(0)
+    }
+
+    /// Return the greatest id of the set.
+    #[inline]
+    pub fn last(&self) -> Option<K> {
+        self.len.checked_sub(1).and_then(|n| self.select(n))
+    }
+
+    fn combine(&self, other: &IdSet<K>, op: Operation) -> IdSet<K> {
+        let mut chunks = Vec::new();
+        let (mut a, mut b) = (
+            self.chunks.iter().peekable(),
+            other.chunks.iter().peekable(),
+        );
+        loop {
+            let order = match (a.peek(), b.peek()) {
+                (Some((x, _)), Some((y, _))) => x.cmp(y),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => break,
+            };
+            match order {
+                Ordering::Less => {
+                    let chunk = a.next().unwrap();
+                    if op != Operation::Intersection {
+                        chunks.push(chunk.clone());
+                    }
+                }
+                Ordering::Greater => {
+                    let chunk = b.next().unwrap();
+                    if op == Operation::Union {
+                        chunks.push(chunk.clone());
+                    }
+                }
+                Ordering::Equal => {
+                    let ((high, x), (_, y)) = (a.next().unwrap(), b.next().unwrap());
+                    let chunk = x.combine(y, op);
+                    if chunk.len() != 0 {
+                        chunks.push((*high, chunk));
+                    }
+                }
+            }
+        }
+        let len = chunks.iter().map(|(_, chunk)| chunk.len()).sum();
+        IdSet {
+            chunks,
+            len,
+            key: PhantomData,
+        }
+    }
+
+    /// Return the set of the ids which are in `self` or in `other`.
+    #[inline]
+    pub fn union(&self, other: &IdSet<K>) -> IdSet<K> {
+        self.combine(other, Operation::Union)
+    }
+
+    /// Return the set of the ids which are in `self` and in `other`.
+    #[inline]
+    pub fn intersection(&self, other: &IdSet<K>) -> IdSet<K> {
+        self.combine(other, Operation::Intersection)
+    }
+
+    /// Return the set of the ids which are in `self` but not in `other`.
+    #[inline]
+    pub fn difference(&self, other: &IdSet<K>) -> IdSet<K> {
+        self.combine(other, Operation::Difference)
+    }
+
+    /// Return an iterator over the ids of the set, in order.
+    pub fn iter(&self) -> IdSetIter<'_, K> {
+        IdSetIter {
+            chunks: self.chunks.iter(),
+            current: None,
+            len: self.len,
+            key: PhantomData,
+        }
+    }
+
+    /// Return the number of bytes used by the high bits of a chunk in the byte format.
+    fn high_bytes() -> usize {
+        (K::Raw::BITS.saturating_sub(16) as usize).div_ceil(8)
+    }
+
+    /// Write the set in a compact byte format, read by [`from_bytes`](#method.from_bytes).
+    ///
+    /// The format (little-endian) is : a version byte, the size in bytes of the ids, 1 if the ids
+    /// are signed (0 otherwise), the number of chunks on 4 bytes, then for each chunk its high
+    /// bits, its kind (0 : array, 1 : bitset) and its content (the number of values on 2 bytes and
+    /// the values for an array, 1024 words of 8 bytes for a bitset).
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let high_bytes = IdSet::<K>::high_bytes();
+        let mut bytes = Vec::new();
+        bytes.push(FORMAT_VERSION);
+        bytes.push((K::Raw::BITS / 8) as u8);
+        bytes.push((sign_bit::<K>() != 0) as u8);
+        bytes.extend_from_slice(&(self.chunks.len() as u32).to_le_bytes());
+        for (high, chunk) in &self.chunks {
+            bytes.extend_from_slice(&high.to_le_bytes()[..high_bytes]);
+            match chunk {
+                Chunk::Array(values) => {
+                    bytes.push(ARRAY);
+                    bytes.extend_from_slice(&(values.len() as u16).to_le_bytes());
+                    for value in values {
+                        bytes.extend_from_slice(&value.to_le_bytes());
+                    }
+                }
+                Chunk::Bitset(words, _) => {
+                    bytes.push(BITSET);
+                    for word in words.iter() {
+                        bytes.extend_from_slice(&word.to_le_bytes());
+                    }
+                }
+            }
+        }
+        bytes
+    }
+
+    /// Read a set written by [`to_bytes`](#method.to_bytes) for the same type of ids.
+    pub fn from_bytes(bytes: &[u8]) -> Result<IdSet<K>, IdSetDecodeError> {
+        let mut reader = Reader(bytes);
+        if reader.take(1)?[0] != FORMAT_VERSION {
+            return Err(IdSetDecodeError::Invalid);
+        }
+        if u32::from(reader.take(1)?[0]) * 8 != K::Raw::BITS {
+            return Err(IdSetDecodeError::WrongIdSize);
+        }
+        let signed = reader.take(1)?[0];
+        if signed > 1 {
+            return Err(IdSetDecodeError::Invalid);
+        }
+        if (signed == 1) != (sign_bit::<K>() != 0) {
+            return Err(IdSetDecodeError::WrongIdSize);
+        }
+        let count = reader.u32()?;
+
+        let high_bytes = IdSet::<K>::high_bytes();
+        let max_high = K::Raw::MAX.to_bits() >> 16 | sign_bit::<K>() >> 16;
+        let mut set = IdSet::new();
+        for _ in 0..count {
+            let mut high = [0; 16];
+            high[..high_bytes].copy_from_slice(reader.take(high_bytes)?);
+            let high = u128::from_le_bytes(high);
+            let sorted = match set.chunks.last() {
+                Some((last, _)) => high > *last,
+                None => true,
+            };
+            if !sorted || high > max_high {
+                return Err(IdSetDecodeError::Invalid);
+            }
+            let chunk = match reader.take(1)?[0] {
+                ARRAY => {
+                    let len = reader.u16()? as usize;
+                    let mut values = Vec::with_capacity(len);
+                    for _ in 0..len {
+                        values.push(reader.u16()?);
+                    }
+                    let sorted = values.windows(2).all(|pair| pair[0] < pair[1]);
+                    // The ids of less than 16 bits have less than 16 low bits.
+                    let fits =
+                        K::Raw::BITS >= 16 || values.iter().all(|low| low >> K::Raw::BITS == 0);
+                    if len == 0 || len > ARRAY_MAX || !sorted || !fits {
+                        return Err(IdSetDecodeError::Invalid);
+                    }
+                    Chunk::Array(values)
+                }
+                // A chunk of ids of less than 16 bits never has enough values for a bitset.
+                BITSET if K::Raw::BITS < 16 => return Err(IdSetDecodeError::Invalid),
+                BITSET => {
+                    let mut words = Box::new([0; WORDS]);
+                    for word in words.iter_mut() {
+                        *word = reader.u64()?;
+                    }
+                    let chunk = Chunk::from_bitset(words);
+                    if let Chunk::Array(_) = chunk {
+                        return Err(IdSetDecodeError::Invalid);
+                    }
+                    chunk
+                }
+                _ => return Err(IdSetDecodeError::Invalid),
+            };
+            set.len += chunk.len();
+            set.chunks.push((high, chunk));
+        }
+        if !reader.0.is_empty() {
+            return Err(IdSetDecodeError::Invalid);
+        }
+        Ok(set)
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, count: usize) -> Result<&'a [u8], IdSetDecodeError> {
+        if self.0.len() < count {
+            return Err(IdSetDecodeError::UnexpectedEnd);
+        }
+        let (bytes, rest) = self.0.split_at(count);
+        self.0 = rest;
+        Ok(bytes)
+    }
+
+    fn u16(&mut self) -> Result<u16, IdSetDecodeError> {
+        let mut bytes = [0; 2];
+        bytes.copy_from_slice(self.take(2)?);
+        Ok(u16::from_le_bytes(bytes))
+    }
+
+    fn u32(&mut self) -> Result<u32, IdSetDecodeError> {
+        let mut bytes = [0; 4];
+        bytes.copy_from_slice(self.take(4)?);
+        Ok(u32::from_le_bytes(bytes))
+    }
+
+    fn u64(&mut self) -> Result<u64, IdSetDecodeError> {
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(self.take(8)?);
+        Ok(u64::from_le_bytes(bytes))
+    }
+}
+
+/// The error returned by [`IdSet::from_bytes`](struct.IdSet.html#method.from_bytes).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum IdSetDecodeError {
+    /// The bytes end in the middle of the set.
+    UnexpectedEnd,
+    /// The set has been written for ids of another size or signedness.
+    WrongIdSize,
+    /// The bytes are not a set written by `IdSet::to_bytes`.
+    Invalid,
+}
+
+impl fmt::Display for IdSetDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IdSetDecodeError::UnexpectedEnd => f.write_str("unexpected end of the id set bytes"),
+            IdSetDecodeError::WrongIdSize => {
+                f.write_str("the id set has been written for ids of another type")
+            }
+            IdSetDecodeError::Invalid => f.write_str("invalid id set bytes"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for IdSetDecodeError {}
+
+impl<K: NumId> Default for IdSet<K> {
+    #[inline]
+    fn default() -> IdSet<K> {
+        IdSet::new()
+    }
+}
+
+impl<K: NumId> Clone for IdSet<K> {
+    fn clone(&self) -> IdSet<K> {
+        IdSet {
+            chunks: self.chunks.clone(),
+            len: self.len,
+            key: PhantomData,
+        }
+    }
+}
+
+impl<K: NumId> PartialEq for IdSet<K> {
+    fn eq(&self, other: &IdSet<K>) -> bool {
+        self.chunks == other.chunks
+    }
+}
+
+impl<K: NumId> Eq for IdSet<K> {}
+
+impl<K: NumId> fmt::Debug for IdSet<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<K: NumId> FromIterator<K> for IdSet<K> {
+    fn from_iter<T: IntoIterator<Item = K>>(iter: T) -> IdSet<K> {
+        let mut set = IdSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<K: NumId> Extend<K> for IdSet<K> {
+    fn extend<T: IntoIterator<Item = K>>(&mut self, iter: T) {
+        for key in iter {
+            self.insert(key);
+        }
+    }
+}
+
+impl<'a, K: NumId> IntoIterator for &'a IdSet<K> {
+    type Item = K;
+    type IntoIter = IdSetIter<'a, K>;
+
+    #[inline]
+    fn into_iter(self) -> IdSetIter<'a, K> {
+        self.iter()
+    }
+}
+
+impl<K: NumId> BitOr for &IdSet<K> {
+    type Output = IdSet<K>;
+
+    #[inline]
+    fn bitor(self, other: &IdSet<K>) -> IdSet<K> {
+        self.union(other)
+    }
+}
+
+impl<K: NumId> BitAnd for &IdSet<K> {
+    type Output = IdSet<K>;
+
+    #[inline]
+    fn bitand(self, other: &IdSet<K>) -> IdSet<K> {
+        self.intersection(other)
+    }
+}
+
+impl<K: NumId> Sub for &IdSet<K> {
+    type Output = IdSet<K>;
+
+    #[inline]
+    fn sub(self, other: &IdSet<K>) -> IdSet<K> {
+        self.difference(other)
+    }
+}
+
+/// An iterator over the values of a chunk.
+struct ChunkIter<'a> {
+    chunk: &'a Chunk,
+    /// The index in the array, or the index of `word` in the bitset.
+    index: usize,
+    /// The bits of the current word not yet returned.
+    word: u64,
+}
+
+impl<'a> ChunkIter<'a> {
+    fn new(chunk: &'a Chunk) -> ChunkIter<'a> {
+        let word = match chunk {
+            Chunk::Array(_) => 0,
+            Chunk::Bitset(words, _) => words[0],
+        };
+        ChunkIter {
+            chunk,
+            index: 0,
+            word,
+        }
+    }
+}
+
+impl Iterator for ChunkIter<'_> {
+    type Item = u16;
+
+    fn next(&mut self) -> Option<u16> {
+        match self.chunk {
+            Chunk::Array(values) => {
+                let value = values.get(self.index).copied();
+                self.index += 1;
+                value
+            }
+            Chunk::Bitset(words, _) => {
+                while self.word == 0 {
+                    self.index += 1;
+                    self.word = *words.get(self.index)?;
+                }
+                let bit = self.word.trailing_zeros();
+                self.word &= self.word - 1;
+                Some((self.index * 64) as u16 + bit as u16)
+            }
+        }
+    }
+}
+
+/// An iterator over the ids of an [`IdSet`](struct.IdSet.html), in order.
+pub struct IdSetIter<'a, K: NumId> {
+    chunks: core::slice::Iter<'a, (u128, Chunk)>,
+    current: Option<(u128, ChunkIter<'a>)>,
+    len: usize,
+    key: PhantomData<fn() -> K>,
+}
+
+impl<K: NumId> Iterator for IdSetIter<'_, K> {
+    type Item = K;
+
+    fn next(&mut self) -> Option<K> {
+        loop {
+            if let Some((high, values)) = &mut self.current {
+                if let Some(low) = values.next() {
+                    self.len -= 1;
+                    return Some(join(*high, low));
+                }
+            }
+            let (high, chunk) = self.chunks.next()?;
+            self.current = Some((*high, ChunkIter::new(chunk)));
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<K: NumId> ExactSizeIterator for IdSetIter<'_, K> {}
+
+impl<K: NumId> FusedIterator for IdSetIter<'_, K> {}
@@ -0,0 +1,409 @@
+//! A vector indexed by ids, the keys are created by the vector from the positions of the values.
+
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt;
+use core::iter::{FromIterator, FusedIterator};
+use core::marker::PhantomData;
+use core::ops::{Index, IndexMut};
+
+use crate::num_id::NumId;
+use crate::primitive::Primitive;
+use crate::range::IdRange;
+
+/// The smallest value of `T`.
+#[inline]
+fn min_value<T: Primitive>() -> T {
+    T::MAX.wrapping_add(T::ONE)
+}
+
+/// Return the key of the position `index`, the value `MIN + index`.
+#[inline]
+fn index_key<K: NumId>(index: usize) -> K {
+    K::__from_raw(min_value::<K::Raw>().wrapping_add(K::Raw::from_bits(index as u128)))
+}
+
+/// Panic if the key of the position `index` is greater than `INITIAL_VALUE`.
+#[inline]
+fn check_index<K: NumId>(index: usize) {
+    if index as u128 > K::INITIAL_VALUE.wrapping_sub(min_value()).to_bits() {
+        panic!("the key of the `IdVec` would be greater than INITIAL_VALUE");
+    }
+}
+
+/// Return the position of `key`, or `None` if `key` is above `INITIAL_VALUE`.
+#[inline]
+fn key_index<K: NumId>(key: K) -> Option<usize> {
+    if key.value() > K::INITIAL_VALUE {
+        return None;
+    }
+    usize::try_from(key.value().wrapping_sub(min_value()).to_bits()).ok()
+}
+
+/// A vector of values of type `V` indexed by ids of type `K`, like the `IndexVec` of rustc.
+///
+/// The key of the value at the position `i` is the id of value `MIN + i` (`i` for the unsigned
+/// types), the keys are the values accepted by `create_lower`, from the smallest value of the type
+/// to `INITIAL_VALUE`. The ids created by `new()` are above `INITIAL_VALUE`, so they are never
+/// keys of the vector, and the "current value" of the type is never read or modified.
+///
+/// The vector holds at most `INITIAL_VALUE - MIN + 1` values : with the default `INITIAL_VALUE`
+/// of an unsigned type (0), the only key is 0 and the second `push` panics. The type must be
+/// declared with an `INITIAL_VALUE` large enough for the keys, for example
+/// `numid!(struct NodeId(u32) -> 1 << 24)` gives 2^24 keys and `new()` starts after them.
+///
+/// # Examples
+/// ```
+/// use numid::{numid, IdVec};
+///
+/// numid!(struct NodeId(u32) -> 1 << 24);
+///
+/// let mut nodes = IdVec::new();
+/// let root: NodeId = nodes.push("root");
+/// let leaf = nodes.push("leaf");
+///
+/// assert_eq!(root.value(), 0);
+/// assert_eq!(nodes[leaf], "leaf");
+/// assert_eq!(nodes.indices().collect::<Vec<_>>(), vec![root, leaf]);
+/// assert!(!nodes.contains_key(NodeId::new()));
+/// ```
+pub struct IdVec<K: NumId, V> {
+    values: Vec<V>,
+    key: PhantomData<fn(K) -> K>,
+}
+
+impl<K: NumId, V> IdVec<K, V> {
+    /// Create an empty vector.
+    #[inline]
+    pub fn new() -> IdVec<K, V> {
+        IdVec::with_capacity(0)
+    }
+
+    /// Create an empty vector with room for `capacity` values.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> IdVec<K, V> {
+        IdVec::from_vec(Vec::with_capacity(capacity))
+    }
+
+    /// Create a vector from the values of `values`, the key of the first value is the smallest
+    /// value of the type.
+    ///
+    /// # Panics
+    /// panic if the key of the last value would be greater than `INITIAL_VALUE`
+    #[inline]
+    pub fn from_vec(values: Vec<V>) -> IdVec<K, V> {
+        if let Some(last) = values.len().checked_sub(1) {
+            check_index::<K>(last);
+        }
+        IdVec {
+            values,
+            key: PhantomData,
+        }
+    }
+
+    /// Return the number of values of the vector.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Return true if the vector contains no value.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Return the key of the next value pushed.
+    ///
+    /// # Panics
+    /// panic if the key would be greater than `INITIAL_VALUE`
+    pub fn next_key(&self) -> K {
+        check_index::<K>(self.values.len());
+        index_key(self.values.len())
+    }
+
+    /// Append a value to the vector and return its key.
+    ///
+    /// # Panics
+    /// panic if the key would be greater than `INITIAL_VALUE`
+    pub fn push(&mut self, value: V) -> K {
+        let key = self.next_key();
+        self.values.push(value);
+        key
+    }
+
+    /// Remove the last value of the vector and return it with its key.
+    #[inline]
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        let value = self.values.pop()?;
+        Some((index_key(self.values.len()), value))
+    }
+
+    /// Return the key of the last value.
+    #[inline]
+    pub fn last_key(&self) -> Option<K> {
+        self.values.len().checked_sub(1).map(index_key)
+    }
+
+    /// Return true if `key` is the key of a value of the vector.
+    #[inline]
+    pub fn contains_key(&self, key: K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Return a reference to the value of `key`.
+    #[inline]
+    pub fn get(&self, key: K) -> Option<&V> {
+        self.values.get(key_index(key)?)
+    }
+
+    /// Return a mutable reference to the value of `key`.
+    #[inline]
+    pub fn get_mut(&mut self, key: K) -> Option<&mut V> {
+        self.values.get_mut(key_index(key)?)
+    }
+
+    /// Return the values as a slice, in the order of the keys.
+    #[inline]
+    pub fn as_slice(&self) -> &[V] {
+        &self.values
+    }
+
+    /// Return the values as a mutable slice, in the order of the keys.
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [V] {
+        &mut self.values
+    }
+
+    /// Return the vector of the values.
+    #[inline]
+    pub fn into_vec(self) -> Vec<V> {
+        self.values
+    }
+
+    /// Return an iterator over the values, in the order of the keys.
+    #[inline]
+    pub fn iter(&self) -> core::slice::Iter<'_, V> {
+        self.values.iter()
+    }
+
+    /// Return an iterator over mutable references to the values, in the order of the keys.
+    #[inline]
+    pub fn iter_mut(&mut self) -> core::slice::IterMut<'_, V> {
+        self.values.iter_mut()
+    }
+
+    /// Return an iterator over the keys and the values.
+    #[inline]
+    pub fn iter_enumerated(&self) -> IdVecIter<'_, K, V> {
+        IdVecIter {
+            values: self.values.iter().enumerate(),
+            key: PhantomData,
+        }
+    }
+
+    /// Return an iterator over the keys and mutable references to the values.
+    #[inline]
+    pub fn iter_enumerated_mut(&mut self) -> IdVecIterMut<'_, K, V> {
+        IdVecIterMut {
+            values: self.values.iter_mut().enumerate(),
+            key: PhantomData,
+        }
+    }
+
+    /// Return the keys of the values.
+    #[inline]
+    pub fn indices(&self) -> IdRange<K> {
+        IdRange::__new(min_value(), self.values.len(), K::Raw::ONE)
+    }
+}
+
+impl<K: NumId, V> Default for IdVec<K, V> {
+    #[inline]
+    fn default() -> IdVec<K, V> {
+        IdVec::new()
+    }
+}
+
+impl<K: NumId, V: Clone> Clone for IdVec<K, V> {
+    fn clone(&self) -> IdVec<K, V> {
+        IdVec::from_vec(self.values.clone())
+    }
+}
+
+impl<K: NumId, V: fmt::Debug> fmt::Debug for IdVec<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter_enumerated()).finish()
+    }
+}
+
+impl<K: NumId, V: PartialEq> PartialEq for IdVec<K, V> {
+    fn eq(&self, other: &IdVec<K, V>) -> bool {
+        self.values == other.values
+    }
+}
+
+impl<K: NumId, V: Eq> Eq for IdVec<K, V> {}
+
+/// # Panics
+/// panic if the key of the last value would be greater than `INITIAL_VALUE`
+impl<K: NumId, V> From<Vec<V>> for IdVec<K, V> {
+    #[inline]
+    fn from(values: Vec<V>) -> IdVec<K, V> {
+        IdVec::from_vec(values)
+    }
+}
+
+/// # Panics
+/// panic if the vector contains no value for the id
+impl<K: NumId, V> Index<K> for IdVec<K, V> {
+    type Output = V;
+
+    #[inline]
+    fn index(&self, key: K) -> &V {
+        match self.get(key) {
+            Some(value) => value,
+            None => panic!("no value for the id {:?}", key),
+        }
+    }
+}
+
+/// # Panics
+/// panic if the vector contains no value for the id
+impl<K: NumId, V> IndexMut<K> for IdVec<K, V> {
+    #[inline]
+    fn index_mut(&mut self, key: K) -> &mut V {
+        match self.get_mut(key) {
+            Some(value) => value,
+            None => panic!("no value for the id {:?}", key),
+        }
+    }
+}
+
+impl<K: NumId, V> FromIterator<V> for IdVec<K, V> {
+    fn from_iter<T: IntoIterator<Item = V>>(iter: T) -> IdVec<K, V> {
+        let mut vec = IdVec::new();
+        vec.extend(iter);
+        vec
+    }
+}
+
+/// # Panics
+/// panic if a key would be greater than `INITIAL_VALUE`
+impl<K: NumId, V> Extend<V> for IdVec<K, V> {
+    fn extend<T: IntoIterator<Item = V>>(&mut self, iter: T) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<'a, K: NumId, V> IntoIterator for &'a IdVec<K, V> {
+    type Item = &'a V;
+    type IntoIter = core::slice::Iter<'a, V>;
+
+    #[inline]
+    fn into_iter(self) -> core::slice::Iter<'a, V> {
+        self.iter()
+    }
+}
+
+impl<'a, K: NumId, V> IntoIterator for &'a mut IdVec<K, V> {
+    type Item = &'a mut V;
+    type IntoIter = core::slice::IterMut<'a, V>;
+
+    #[inline]
+    fn into_iter(self) -> core::slice::IterMut<'a, V> {
+        self.iter_mut()
+    }
+}
+
+impl<K: NumId, V> IntoIterator for IdVec<K, V> {
+    type Item = V;
+    type IntoIter = alloc::vec::IntoIter<V>;
+
+    #[inline]
+    fn into_iter(self) -> alloc::vec::IntoIter<V> {
+        self.values.into_iter()
+    }
+}
+
+/// An iterator over the keys and the values of an [`IdVec`](struct.IdVec.html).
+pub struct IdVecIter<'a, K: NumId, V> {
+    values: core::iter::Enumerate<core::slice::Iter<'a, V>>,
+    key: PhantomData<fn() -> K>,
+}
+
+impl<'a, K: NumId, V> Iterator for IdVecIter<'a, K, V> {
+    type Item = (K, &'a V);
+
+    #[inline]
+    fn next(&mut self) -> Option<(K, &'a V)> {
+        self.values
+            .next()
+            .map(|(index, value)| (index_key(index), value))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.values.size_hint()
+    }
+}
+
+impl<'a, K: NumId, V> DoubleEndedIterator for IdVecIter<'a, K, V> {
+    #[inline]
+    fn next_back(&mut self) -> Option<(K, &'a V)> {
+        self.values
+            .next_back()
+            .map(|(index, value)| (index_key(index), value))
+    }
+}
+
+impl<K: NumId, V> ExactSizeIterator for IdVecIter<'_, K, V> {}
+
+impl<K: NumId, V> FusedIterator for IdVecIter<'_, K, V> {}
+
+impl<K: NumId, V> Clone for IdVecIter<'_, K, V> {
+    fn clone(&self) -> Self {
+        IdVecIter {
+            values: self.values.clone(),
+            key: PhantomData,
+        }
+    }
+}
+
+/// An iterator over the keys and mutable references to the values of an
+/// [`IdVec`](struct.IdVec.html).
+pub struct IdVecIterMut<'a, K: NumId, V> {
+    values: core::iter::Enumerate<core::slice::IterMut<'a, V>>,
+    key: PhantomData<fn() -> K>,
+}
+
+impl<'a, K: NumId, V> Iterator for IdVecIterMut<'a, K, V> {
+    type Item = (K, &'a mut V);
+
+    #[inline]
+    fn next(&mut self) -> Option<(K, &'a mut V)> {
+        self.values
+            .next()
+            .map(|(index, value)| (index_key(index), value))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.values.size_hint()
+    }
+}
+
+impl<'a, K: NumId, V> DoubleEndedIterator for IdVecIterMut<'a, K, V> {
+    #[inline]
+    fn next_back(&mut self) -> Option<(K, &'a mut V)> {
+        self.values
+            .next_back()
+            .map(|(index, value)| (index_key(index), value))
+    }
+}
+
+impl<K: NumId, V> ExactSizeIterator for IdVecIterMut<'_, K, V> {}
+
+impl<K: NumId, V> FusedIterator for IdVecIterMut<'_, K, V> {}
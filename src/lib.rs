@@ -13,9 +13,14 @@ to `INITIAL_VALUE` is free to use, and multiple instances with the same value
 can be created.
 
 Use `new`, `default` or `create_maybe` for creating unique id.
+The "current value" is stored in an atomic integer (or behind a spin lock for the types
+without native atomic, like `u128`), so ids are unique even when created from several threads.
 
 Use `create_lower` or `const_create_lower` for creating free id.
 
+The fallible methods (`try_new`, `try_create`, `try_create_lower`) return an [`Error`](enum.Error.html)
+explaining why the id can't be created, `const_try_create_lower` return an `Option`.
+
 Id created with `reproduce` are unique or free depending on the id used.
 
 # Syntax
@@ -24,6 +29,7 @@ Id created with `reproduce` are unique or free depending on the id used.
 numid!([pub] struct NAME [(TYPE)] [-> CONSTANT]);
 ```
 If not indicated, TYPE=`u64` and CONSTANT=`0`.
+TYPE must be a primitive integer type (see [`Primitive`](trait.Primitive.html)).
 
 # Attributes
 
@@ -63,29 +69,219 @@ The behavior of the `Clone` trait can be modified with built-in attribut :
 - `@CloneIsClone` : default behavior, can be ignored
 - `@CloneIsNew` : `clone()` call `new()`
 - `@CloneIsReproduce` : `clone()` call `reproduce()`
-This built-in attribut must be placed between the attributs and the visibility argument.
+
+The behavior of `new` when the "current value" reached `MAX_VALUE` can be modified with built-in attribut :
+- `@OverflowPanic` : default behavior, `new()` panics
+- `@OverflowSaturate` : the "current value" stays at `MAX_VALUE` and `new()` panics
+- `@OverflowWrap` : `new()` wraps back to `INITIAL_VALUE + 1` and increments `epoch()`
+
+In all cases, `try_new()` return `Err(Error::Exhausted)` instead of creating an id with a value already used
+(except for `@OverflowWrap`). See [`Overflow`](enum.Overflow.html).
+
+With the `serde` feature, the `@SerdeReplaceCurrent` built-in attribut makes the deserialization call
+`replace_current_value` with the loaded value, so the ids created after loading never collide with the ids
+loaded.
+The `@SerdeAsString` built-in attribut makes the id serialized as a decimal string (useful for JSON,
+where the integers above 2^53 lose precision), the deserialization accepts a string or an integer.
+
+With the `alloc` feature, the `@Pool` built-in attribut adds the `alloc()` and `free(id)` methods :
+`alloc()` creates an id with the lowest value freed with `free(id)`, or with a new value if no value is free,
+the freed values are stored in a thread-safe free list. `current_value()` stays the highest value created.
+Once `MAX_VALUE` is reached and no value is free, `alloc()` returns `Err(Error::Exhausted)`, even with
+`@OverflowWrap` : wrapping would create again the ids still in use.
+The pool doesn't track the ids in use, so the ids of a `@Pool` type must be created with `alloc()` only :
+an id created with `new()` and given to `free(id)` would be created again by `alloc()`.
+
+With the `std` feature, the `@ThreadCached(N)` built-in attribut makes each thread reserve blocks of `N` values
+(see `reserve`) and create its ids from its own block, without touching the shared "current value" :
+the ids stay unique, but **they are only ordered inside a thread**, an id created after another one in a different
+thread can have a lower value. The "current value" includes the values reserved and not yet used.
+
+The "current value" is stored in a [`Counter`](struct.Counter.html) in a `static` by default, the
+`@Source(path::to::SOURCE)` built-in attribut makes the type use the `static` `SOURCE` instead, which can be any
+type implementing [`IdSource`](trait.IdSource.html) (a counter stored in a file or a database, a mock...).
+
+The `FromStr` and `TryFrom<&str>` traits are implemented for the `struct`, the value can be written in decimal,
+or in hexadecimal, octal or binary with the `0x`, `0o` or `0b` prefix (the digits are the bits of the value, like
+the output of the `{:#x}`, `{:#o}` and `{:#b}` formats). The error is a [`ParseError`](enum.ParseError.html).
+The values accepted can be restricted with built-in attribut :
+- `@ParseAny` : default behavior, any value is accepted, the "current value" is not modified
+- `@ParseLower` : only the values accepted by `create_lower` are accepted
+- `@ParseCreate` : the id is created with `try_create`, so the "current value" is replaced by the value parsed
+  (only by `FromStr`, the decoding methods below never modify the "current value")
+
+The `to_base62()` and `to_crockford32()` methods return a compact form of the value, as an
+[`EncodedId`](struct.EncodedId.html) (a string stored inline, usable with `Display`, even without `alloc`),
+for example for the URLs. They are decoded with `from_base62` and `from_crockford32`, which accept the same
+values as `FromStr`. Crockford's base32 is case-insensitive and accepts `I`, `L` and `O` for `1`, `1` and `0`.
+
+The `obfuscate(key)` method returns the value of the id permuted by a Feistel network keyed by `key` (a `u64`),
+so the ids shown to the users don't reveal how many ids were created, and `deobfuscate(value, key)` returns the
+original id. The permutation is a bijection over all the values of TYPE, and is the same on every platform.
+
+The `@Prefix("usr_")` built-in attribut makes `Display` write `usr_42` instead of `42` and `FromStr` expect
+this form, a string with another prefix or without prefix is refused with `ParseError::WrongPrefix`
+(`usr_0x2a` is also accepted). The `Debug`, `Binary`, `Octal`, `LowerHex` and `UpperHex` traits don't use the prefix.
+Without the `display` feature, the prefix is still expected by `FromStr` and available as `PREFIX`.
+
+The `@Step(N)` built-in attribut makes `new()` increase the "current value" by `N` instead of 1 (`N` must be
+positive) : with `numid!(@Step(2) struct Even(u32));` the ids have the values 2, 4, 6...
+The ids of a block returned by `reserve` are also separated by `N`.
+
+These built-in attributs must be placed between the attributs and the visibility argument.
+
+# Independent id spaces
+
+The "current value" used by `new` is shared by the whole process. A [`Generator`](struct.Generator.html)
+(with the `alloc` feature) owns its own "current value", so several independent id spaces of the same type
+can be used, for example one per document or one per test.
+
+# Persistence
+
+The "current value" lives in memory, it restarts at `INITIAL_VALUE` with the process. `snapshot()` returns
+the "current value" and the epoch as a [`CounterSnapshot`](struct.CounterSnapshot.html), and `restore(snapshot)`
+moves them forward after a restart, so the new ids don't reuse the values of the ids already stored.
+
+With the `std` feature, [`save_high_water_mark`](fn.save_high_water_mark.html) writes the "current value" to a
+file atomically (through a temporary file renamed) and [`load_high_water_mark`](fn.load_high_water_mark.html)
+reads it back at startup and calls `replace_current_value`.
+
+Saving the "current value" after every id is slow, a [`HiLoGenerator`](struct.HiLoGenerator.html) (with the
+`std` feature) reserves blocks of ids instead : the end of the block is written to a file before its first id
+is created, so the file is only written once per block. After a crash the rest of the block is skipped, but no
+id is created twice.
+
+On unix, a [`SharedGenerator`](struct.SharedGenerator.html) (with the `std` feature) stores its "current value"
+in a file locked with `flock` : the processes of a host using the same file share one sequence of ids.
+
+With the `rusqlite` feature, a [`SqliteSource`](struct.SqliteSource.html) given with `@Source(SOURCE)` stores
+the "current value" of the type in a row of a SQLite database : `new()` takes its values from blocks reserved
+in a transaction, and the ids are never reused across the runs of the program.
+
+These generators and this source never create a value twice : the overflow policy of the type is not applied,
+they return an error (`Error::Exhausted`) once `MAX_VALUE` is reached. The generators own their "current value"
+like a `Generator`, the "current value" used by `new()` is not modified.
+
+# Collections
+
+With the `alloc` feature, an [`IdMap`](struct.IdMap.html) maps the ids to values like a `HashMap`, but stores
+the values in a vector indexed by `(value - INITIAL_VALUE) / STEP` : the ids created with `new()` are dense,
+so the map is smaller and faster than a hash map.
+
+An [`IdSet`](struct.IdSet.html) is a compressed set of ids (like the roaring bitmaps) with the union,
+the intersection, the difference, `rank` and `select`, it is iterated in the order of the ids and can be
+written to a compact byte format with `to_bytes`.
+
+An [`IdVec`](struct.IdVec.html) is a vector indexed by ids, like the `IndexVec` of rustc : `push` returns the
+key of the value, the keys are the values below `INITIAL_VALUE` (free for `create_lower`), so they never clash
+with the ids created by `new()`. With the default `INITIAL_VALUE` of an unsigned type (0), the vector holds a
+single value : the type used as key must reserve room below `new()`, for example with
+`numid!(struct NodeId(u32) -> 1 << 24);` (2^24 keys, `new()` starts at 2^24 + 1).
+
+# Generational ids
+
+`numid!(@Generational [pub] struct NAME [(TYPE)]);` generates an id made of an index and a generation
+(both of type TYPE), for the arenas which reuse the indices of the freed elements.
+These ids are created by a [`GenerationalAllocator`](struct.GenerationalAllocator.html) (with the `alloc`
+feature), which reuses the freed indices with a bumped generation and can tell whether a stale id
+is still alive. They are formatted as `{index}v{generation}` with `Display`, `Binary`, `Octal`,
+`LowerHex` and `UpperHex`.
+
+# Snowflake ids
+
+`numid!(@Snowflake(node_bits = N, seq_bits = S) [pub] struct NAME);` generates a `u64` id made of a timestamp
+in milliseconds, a node id of `N` bits and a sequence of `S` bits (`@Snowflake` alone uses 10 and 12 bits),
+so several processes can create unique and time-ordered ids without sharing a counter, as long as they use
+different node ids (set with `set_node`). `new()` packs the time of the clock, the node id and the number of ids
+already created in the same millisecond, the parts are read back with `timestamp()`, `node()` and `sequence()`.
+
+The clock is [`SystemClock`](struct.SystemClock.html) by default (with the `std` feature), and can be replaced
+with `set_clock` by any [`Clock`](trait.Clock.html), like a [`MockClock`](struct.MockClock.html) in the tests.
+The ids are always increasing : if the clock goes backwards or if the sequence of a millisecond is exhausted,
+the timestamp of the last id is kept or incremented.
 
 # Methods
 
 The following methods are defined for the generated `struct` (only `value` and `reproduce` need a instance) :
 
 - `new` : create a new id
+- `try_new` : create a new id, or return an error if the id space is exhausted
 - `value` : get the id value
 - `current_value` : get the value of the last id or initial_value if no id created
 - `initial_value` : get the value defined when calling `numid!`
+- `reserve` : advance the "current value" once and get a block of new ids, see [`IdRange`](struct.IdRange.html)
+- `remaining` : get the number of ids which can still be created before reaching `MAX_VALUE`
+- `epoch` : get the number of times the "current value" wrapped with `@OverflowWrap`
+- `snapshot` and `restore` : save the "current value" and the epoch, and move them forward after a restart
 - `replace_current_value` : see  [`example::NumId::replace_current_value`](./example/struct.NumId.html#method.replace_current_value)
 - `create_maybe` : see  [`example::NumId::create_maybe`](./example/struct.NumId.html#method.create_maybe)
 - `create_lower` : see  [`example::NumId::create_lower`](./example/struct.NumId.html#method.create_lower)
 - `const_create_lower` : see [`example::NumId::const_create_lower`](./example/struct.NumId.html#method.const_create_lower)
+- `try_create`, `try_create_lower` and `const_try_create_lower` : fallible versions of `create_maybe`
+  and `create_lower`
+- `to_base62`, `from_base62`, `to_crockford32` and `from_crockford32` : compact string encodings of the id
+- `obfuscate` and `deobfuscate` : reversible permutation of the value of the id
 - `reproduce` : see [`example::NumId::reproduce`](./example/struct.NumId.html#method.reproduce)
 
 See [`example::NumId`](./example/struct.NumId.html) for more documentation of  methods generated by `numid!`.
 
+The [`NumId`](trait.NumId.html) trait is implemented by every generated `struct`, for writing generic code
+over any id type :
+
+```rust
+use numid::{numid, NumId};
+
+fn persist<I: NumId>(id: I) -> String {
+    format!("{:?}", id.value())
+}
+
+numid!(struct UserId(u32));
+
+fn main() {
+    assert_eq!(persist(UserId::new()), "1");
+}
+```
+
+# Derive
+
+With the `derive` feature, `#[derive(NumId)]` generates the same API as `numid!` for a tuple struct
+with a single integer field, the built-in attributs are given as options of the `numid` attribute :
+
+```ignore
+use numid::NumId;
+
+#[derive(NumId)]
+#[numid(init = 10, repr = u32, clone = "reproduce", step = 2)]
+struct MyId(u32);
+```
+
+The options are `init = EXPR`, `repr = TYPE`, `clone = "clone" | "new" | "reproduce"`,
+`overflow = "panic" | "saturate" | "wrap"`, `parse = "any" | "lower" | "create"`, `prefix = "..."`, `step = EXPR`, `pool`, `thread_cached = EXPR`,
+`serde_replace_current` and `serde_as_string`. The derive also implements `PartialEq`, `Eq`, `PartialOrd`,
+`Ord`, `Hash` and `Debug`, they must not be derived again.
+
 # Crate feature
 
 This crate provides the `display` feature enabled by default who automatically implemente the `Display` trait
 in the structure generated by the `numid!` macro.  If you want to implemente your own version of `Display`,
 add `default-features = false` in the `dependencies.numid` section of your `Cargo.toml`.
+
+The `alloc` feature provides the [`Generator`](struct.Generator.html), the [`IdMap`](struct.IdMap.html), the
+[`IdSet`](struct.IdSet.html), the [`IdVec`](struct.IdVec.html), the [`GenerationalAllocator`](struct.GenerationalAllocator.html) and the `@Pool` built-in attribut.
+
+The `std` feature implements `std::error::Error` for [`Error`](enum.Error.html) and provides
+the `@ThreadCached(N)` built-in attribut and the [`save_high_water_mark`](fn.save_high_water_mark.html) and
+[`load_high_water_mark`](fn.load_high_water_mark.html) functions and the
+[`HiLoGenerator`](struct.HiLoGenerator.html) (and the [`SharedGenerator`](struct.SharedGenerator.html)
+on unix), it enables `alloc` (and depends on `libc` on unix, for `flock`).
+
+The `serde` feature implements `Serialize` and `Deserialize` for the generated `struct`, the id is
+serialized as its bare integer value. The deserialization doesn't modify the "current value",
+unless the `@SerdeReplaceCurrent` built-in attribut is used.
+
+The `derive` feature provides `#[derive(NumId)]`.
+
+The `rusqlite` feature provides the [`SqliteSource`](struct.SqliteSource.html), it enables `std`.
 */
 
 #![cfg_attr(not(test), no_std)]
@@ -93,10 +289,110 @@ add `default-features = false` in the `dependencies.numid` section of your `Carg
 #![warn(missing_docs)]
 
 #[doc(hidden)]
-pub extern crate const_fn_assert as _const_fn_assert;
+pub extern crate core as _core;
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
+#[cfg(all(feature = "std", not(test)))]
+extern crate std;
+
+#[cfg(feature = "std")]
 #[doc(hidden)]
-pub extern crate core as _core;
+pub extern crate std as _std;
+
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+pub extern crate serde as _serde;
+
+mod counter;
+mod encoding;
+mod error;
+mod generational;
+#[cfg(feature = "alloc")]
+mod generator;
+#[cfg(feature = "std")]
+mod hilo;
+#[cfg(feature = "alloc")]
+mod id_map;
+#[cfg(feature = "alloc")]
+mod id_set;
+#[cfg(feature = "alloc")]
+mod id_vec;
+#[cfg(feature = "std")]
+mod lock;
+mod num_id;
+mod obfuscate;
+mod parse;
+#[cfg(feature = "std")]
+mod persist;
+#[cfg(feature = "alloc")]
+mod pool;
+mod primitive;
+mod range;
+mod serde;
+#[cfg(all(feature = "std", unix))]
+mod shared;
+mod snowflake;
+mod source;
+#[cfg(feature = "rusqlite")]
+mod sqlite;
+#[cfg(feature = "std")]
+mod thread_cached;
+
+pub use crate::counter::{Counter, CounterSnapshot, Overflow};
+#[doc(hidden)]
+pub use crate::counter::{__cached_next, __unwrap_next};
+pub use crate::encoding::EncodedId;
+#[doc(hidden)]
+pub use crate::encoding::{
+    decode_base62 as __decode_base62, decode_crockford32 as __decode_crockford32,
+    encode_base62 as __encode_base62, encode_crockford32 as __encode_crockford32,
+};
+pub use crate::error::{Error, NumIdError};
+#[cfg(feature = "alloc")]
+pub use crate::generational::GenerationalAllocator;
+pub use crate::generational::GenerationalId;
+#[cfg(feature = "alloc")]
+pub use crate::generator::Generator;
+#[cfg(feature = "std")]
+pub use crate::hilo::HiLoGenerator;
+#[cfg(feature = "alloc")]
+pub use crate::id_map::{
+    IdMap, IdMapEntry, IdMapIter, IdMapIterMut, IdMapOccupiedEntry, IdMapVacantEntry,
+};
+#[cfg(feature = "alloc")]
+pub use crate::id_set::{IdSet, IdSetDecodeError, IdSetIter};
+#[cfg(feature = "alloc")]
+pub use crate::id_vec::{IdVec, IdVecIter, IdVecIterMut};
+pub use crate::num_id::NumId;
+#[doc(hidden)]
+pub use crate::obfuscate::{deobfuscate as __deobfuscate, obfuscate as __obfuscate};
+#[cfg(feature = "derive")]
+pub use numid_derive::NumId;
+#[cfg(feature = "alloc")]
+pub use crate::pool::Pool;
+pub use crate::parse::ParseError;
+#[cfg(feature = "std")]
+pub use crate::persist::{load_high_water_mark, save_high_water_mark};
+#[doc(hidden)]
+pub use crate::parse::{parse_value as __parse_value, strip_prefix as __strip_prefix};
+pub use crate::primitive::{AtomicPrimitive, Locked, Primitive};
+pub use crate::range::{IdRange, IdRangeChunks};
+#[cfg(all(feature = "std", unix))]
+pub use crate::shared::SharedGenerator;
+#[cfg(feature = "std")]
+pub use crate::snowflake::SystemClock;
+pub use crate::snowflake::{Clock, MockClock};
+pub use crate::source::IdSource;
+#[cfg(feature = "rusqlite")]
+pub use crate::sqlite::SqliteSource;
+#[doc(hidden)]
+pub use crate::snowflake::Snowflake as __Snowflake;
+
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+pub use crate::serde::deserialize_str_or_int as __deserialize_str_or_int;
 
 /// # Examples
 /// ```
@@ -110,60 +406,161 @@ pub extern crate core as _core;
 /// numid!(struct Id6(u128)); // type specified
 /// numid!(@CloneIsReproduce struct Id7); // with in-macro special attribute
 /// numid!(#[doc(hidden)] @CloneIsNew pub struct Id8(u32) -> 10); // all the thing you can want
+/// numid!(@Generational pub struct Id9(u32)); // generational id
+/// numid!(@OverflowWrap @CloneIsNew struct Id10(u8)); // several in-macro special attributes
+/// numid!(@Snowflake(node_bits = 10, seq_bits = 12) pub struct Id11); // snowflake id
 /// ```
 #[macro_export]
 macro_rules! numid {
+    ($(#[$attr:meta])* @Generational $vis:vis struct $name:ident) => {
+        $crate::__generational_numid!{$(#[$attr])* $vis struct $name(u64)}
+    };
+    ($(#[$attr:meta])* @Generational $vis:vis struct $name:ident($ty:ty)) => {
+        $crate::__generational_numid!{$(#[$attr])* $vis struct $name($ty)}
+    };
+    ($(#[$attr:meta])* @Snowflake $vis:vis struct $name:ident) => {
+        $crate::__snowflake_numid!{$(#[$attr])* $vis struct $name [10, 12]}
+    };
+    ($(#[$attr:meta])* @Snowflake(node_bits = $node_bits:expr, seq_bits = $seq_bits:expr)
+        $vis:vis struct $name:ident) => {
+        $crate::__snowflake_numid!{$(#[$attr])* $vis struct $name [$node_bits, $seq_bits]}
+    };
     ($(#[$attr:meta])* $vis:vis struct $name:ident) => {
-        numid!{$(#[$attr])* @CloneIsClone $vis struct $name(u64) -> 0 }
+        $crate::numid!{@__impl [$(#[$attr])*] [] $vis struct $name(u64) -> 0 }
     };
     ($(#[$attr:meta])* $vis:vis struct $name:ident -> $init_val:expr) => {
-        numid!{$(#[$attr])* @CloneIsClone $vis struct $name(u64) -> $init_val }
+        $crate::numid!{@__impl [$(#[$attr])*] [] $vis struct $name(u64) -> $init_val }
     };
     ($(#[$attr:meta])* $vis:vis struct $name:ident($ty:ty)) => {
-        numid!{$(#[$attr])* @CloneIsClone $vis struct $name($ty) -> 0 }
+        $crate::numid!{@__impl [$(#[$attr])*] [] $vis struct $name($ty) -> 0 }
     };
     ($(#[$attr:meta])* $vis:vis struct $name:ident($ty:ty) -> $init_val:expr) => {
-        numid!{$(#[$attr])* @CloneIsClone $vis struct $name($ty) -> $init_val }
+        $crate::numid!{@__impl [$(#[$attr])*] [] $vis struct $name($ty) -> $init_val }
+    };
+    // The built-in attributes are parsed one by one, `@NAME` or `@NAME(ARGS)`,
+    // and collected as `NAME [ARGS]`.
+    (@__options $attrs:tt [$($mattrs:tt)*] @$mattr:ident($($marg:tt)*) $($rest:tt)*) => {
+        $crate::numid!{@__options $attrs [$($mattrs)* $mattr [$($marg)*]] $($rest)*}
+    };
+    (@__options $attrs:tt [$($mattrs:tt)*] @$mattr:ident $($rest:tt)*) => {
+        $crate::numid!{@__options $attrs [$($mattrs)* $mattr []] $($rest)*}
     };
-    ($(#[$attr:meta])* $(@$mattr:ident)+ $vis:vis struct $name:ident) => {
-        numid!{$(#[$attr])* $(@$mattr)+ $vis struct $name(u64) -> 0 }
+    (@__options $attrs:tt $mattrs:tt $vis:vis struct $name:ident) => {
+        $crate::numid!{@__impl $attrs $mattrs $vis struct $name(u64) -> 0 }
     };
-    ($(#[$attr:meta])* $(@$mattr:ident)+ $vis:vis struct $name:ident -> $init_val:expr) => {
-        numid!{$(#[$attr])* $(@$mattr)+ $vis struct $name(u64) -> $init_val }
+    (@__options $attrs:tt $mattrs:tt $vis:vis struct $name:ident -> $init_val:expr) => {
+        $crate::numid!{@__impl $attrs $mattrs $vis struct $name(u64) -> $init_val }
     };
-    ($(#[$attr:meta])* $(@$mattr:ident)+ $vis:vis struct $name:ident($ty:ty)) => {
-        numid!{$(#[$attr])* $(@$mattr)+ $vis struct $name($ty) -> 0 }
+    (@__options $attrs:tt $mattrs:tt $vis:vis struct $name:ident($ty:ty)) => {
+        $crate::numid!{@__impl $attrs $mattrs $vis struct $name($ty) -> 0 }
     };
-    ($(#[$attr:meta])* $(@$mattr:ident)* $vis:vis struct $name:ident($ty:ty) -> $init_val:expr) => {
+    (@__options $attrs:tt $mattrs:tt $vis:vis struct $name:ident($ty:ty) -> $init_val:expr) => {
+        $crate::numid!{@__impl $attrs $mattrs $vis struct $name($ty) -> $init_val }
+    };
+    (@__impl [$(#[$attr:meta])*] [$($mattr:ident [$($marg:tt)*])*]
+        $vis:vis struct $name:ident($ty:ty) -> $init_val:expr) => {
 
-        /// A numerical id generated with the `numid!` macro.
+        #[doc = $crate::__doc_numid!($($mattr)*)]
         #[warn(non_camel_case_types)]
         #[warn(dead_code)] // rust-lang : issue 66030
         #[derive(Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
         $(#[$attr])* 
         $vis struct $name($ty);
 
+        $crate::numid!{@__body [$($mattr [$($marg)*])*] $name($ty) -> $init_val}
+    };
+    (@__body [$($mattr:ident [$($marg:tt)*])*] $name:ident($ty:ty) -> $init_val:expr) => {
         impl $name {
             /// Constant defined when calling the `numid!` macro (0 if not defined).
             /// The first id created (with `new()` or `default()`) will have value = `INITIAL_VALUE + 1`.
             pub const INITIAL_VALUE: $ty = $init_val;
 
-            #[doc(hidden)]
+            /// The largest value an id can have.
+            pub const MAX_VALUE: $ty = <$ty as $crate::Primitive>::MAX;
+
+            /// The behavior of `new()` and `try_new()` once the "current value" reached `MAX_VALUE`,
+            /// chosen with the `@OverflowPanic` (default), `@OverflowSaturate` or `@OverflowWrap`
+            /// built-in attribute.
+            pub const OVERFLOW: $crate::Overflow = $crate::__overflow_numid!($($mattr)*);
+
+            /// The difference between the values of two successive ids created with `new()`,
+            /// defined with the `@Step(N)` built-in attribute (1 if not defined).
+            pub const STEP: $ty = $crate::__step_numid!($($mattr [$($marg)*])*);
+
+            /// The prefix written before the value by `Display` and expected by `FromStr`,
+            /// defined with the `@Prefix("...")` built-in attribute (empty if not defined).
+            pub const PREFIX: &'static str = $crate::__prefix_numid!($($mattr [$($marg)*])*);
+
+            /// Increase the "current value" by `STEP` and create a new id with value = `current_value()`.
+            /// This is thread-safe : two calls to `new()` never return the same id.
+            ///
+            /// # Panics
+            /// panic if the "current value" is above `MAX_VALUE - STEP` and `OVERFLOW` is not `Overflow::Wrap`,
+            /// see `numid::Overflow`.
+            #[allow(dead_code)]
             #[inline]
-            unsafe fn __get_static_mut() -> &'static mut $ty {
-                static mut CURRENT_VALUE: $ty = $name::INITIAL_VALUE;
-                &mut CURRENT_VALUE
+            pub fn new() -> $name {
+                $name($crate::__unwrap_next($name::__try_next()))
             }
 
-            /// Increment the "current value" and create a new id with value = `current_value()`.
+            /// Increase the "current value" by `STEP` and create a new id with value = `current_value()`.
+            /// Return `Err(Error::Exhausted)` if the "current value" is above `MAX_VALUE - STEP`,
+            /// unless `OVERFLOW` is `Overflow::Wrap` and `INITIAL_VALUE` is not above `MAX_VALUE - STEP`.
             #[allow(dead_code)]
             #[inline]
-            pub fn new() -> $name {
-                $name(unsafe {
-                    let v = $name::__get_static_mut();
-                    *v += 1;
-                    *v
-                })
+            pub fn try_new() -> Result<$name, $crate::NumIdError> {
+                $name::__try_next().map($name)
+            }
+
+            /// Increase the "current value" by `count * STEP` and return the block of the `count` new ids,
+            /// as an iterator creating the ids without touching the "current value".
+            ///
+            /// # Panics
+            /// panic if the block doesn't fit below `MAX_VALUE` and `OVERFLOW` is not
+            /// `Overflow::Wrap`, with `Overflow::Wrap` the block starts at `INITIAL_VALUE + STEP`.
+            #[allow(dead_code)]
+            #[inline]
+            pub fn reserve(count: usize) -> $crate::IdRange<$name> {
+                match $name::try_reserve(count) {
+                    Ok(range) => range,
+                    Err(error) => panic!("{}", error),
+                }
+            }
+
+            /// Fallible version of [`reserve`](#method.reserve),
+            /// return `Err(Error::Exhausted)` instead of panicking.
+            #[allow(dead_code)]
+            pub fn try_reserve(count: usize) -> Result<$crate::IdRange<$name>, $crate::Error> {
+                $crate::IdSource::reserve_block(
+                    $name::__source(),
+                    count,
+                    $name::OVERFLOW,
+                    $name::INITIAL_VALUE,
+                    $name::STEP,
+                )
+                .map(|first| $crate::IdRange::__new(first, count, $name::STEP))
+            }
+
+            /// Return the number of ids which can still be created with `new()`
+            /// before the "current value" reaches `MAX_VALUE`.
+            #[allow(dead_code)]
+            #[inline]
+            pub fn remaining() -> u128 {
+                // `MAX_VALUE - current_value()` doesn't fit in a signed `$ty`, but fits in its bits.
+                let left = <$ty as $crate::Primitive>::wrapping_sub(
+                    $name::MAX_VALUE,
+                    $name::current_value(),
+                );
+                <$ty as $crate::Primitive>::to_bits(left)
+                    / <$ty as $crate::Primitive>::to_bits($name::STEP)
+            }
+
+            /// Return the number of times the "current value" wrapped back to `INITIAL_VALUE`,
+            /// always 0 unless `OVERFLOW` is `Overflow::Wrap`.
+            #[allow(dead_code)]
+            #[inline]
+            pub fn epoch() -> usize {
+                $crate::IdSource::epoch($name::__source())
             }
 
             /// Get the value of the id.
@@ -179,9 +576,7 @@ macro_rules! numid {
             #[allow(dead_code)]
             #[inline]
             pub fn current_value() -> $ty {
-                unsafe {
-                    *$name::__get_static_mut()
-                }
+                $crate::IdSource::current($name::__source())
             }
 
             /// Return INITIAL_VALUE.
@@ -196,14 +591,24 @@ macro_rules! numid {
             /// Return true if the "current value" has been modified.
             #[allow(dead_code)]
             pub fn replace_current_value(value: $ty) -> bool {
-                let cond = value > $name::current_value();
-                if cond {
-                    unsafe {
-                        let v = $name::__get_static_mut();
-                        *v = value;
-                    }
-                }
-                cond
+                $crate::IdSource::observe_max($name::__source(), value)
+            }
+
+            /// Return the "current value" and the epoch, to be saved and given to
+            /// [`restore`](#method.restore) after a restart.
+            #[allow(dead_code)]
+            #[inline]
+            pub fn snapshot() -> $crate::CounterSnapshot<$ty> {
+                $crate::IdSource::snapshot($name::__source())
+            }
+
+            /// Move the "current value" and the epoch forward to `snapshot` if it is ahead of
+            /// them, so the ids created after the call never reuse a value of the snapshot's epoch.
+            /// Return true if the "current value" has been modified.
+            /// It should be called before creating the first id.
+            #[allow(dead_code)]
+            pub fn restore(snapshot: $crate::CounterSnapshot<$ty>) -> bool {
+                $crate::IdSource::restore($name::__source(), snapshot)
             }
 
             /// Return Some id with specified value and replace the "current value" if
@@ -219,6 +624,15 @@ macro_rules! numid {
                 }
             }
 
+            /// Return `Ok` with an id of specified value and replace the "current value" if
+            /// `replace_current_value(value)` is `true`.
+            /// Return `Err(Error::NotGreaterThanCurrent)` otherwise.
+            #[allow(dead_code)]
+            #[inline]
+            pub fn try_create(value: $ty) -> Result<$name, $crate::Error> {
+                $name::create_maybe(value).ok_or($crate::Error::NotGreaterThanCurrent)
+            }
+
             /// Create a id with a precised value, don't increment the "current value".
             /// The value must be inferior or equal as `INITIAL_VALUE` for not
             /// interfering with the id system.
@@ -228,22 +642,45 @@ macro_rules! numid {
             #[allow(dead_code)]
             #[inline]
             pub fn create_lower(value: $ty) -> $name {
-                assert!(value <= $name::initial_value());
-                $name(value)
+                match $name::try_create_lower(value) {
+                    Ok(id) => id,
+                    Err(error) => panic!("{}", error),
+                }
+            }
+
+            /// Fallible version of [`create_lower`](#method.create_lower),
+            /// return `Err(Error::AboveInitialValue)` if `value > INITIAL_VALUE`.
+            #[allow(dead_code)]
+            #[inline]
+            pub fn try_create_lower(value: $ty) -> Result<$name, $crate::Error> {
+                $name::const_try_create_lower(value).ok_or($crate::Error::AboveInitialValue)
             }
 
             /// Const version of [`create_lower`](#method.create_lower),
-            /// can be used in a const environment. In a non-const environment, give a
-            /// `index: out of bounds` panic message, `create_lower` giving a more descriptive
-            /// message is therefore preferred.
+            /// can be used in a const environment.
             ///
             /// # Panics
-            /// panic if `value > INITIAL_VALUE`
+            /// panic if `value > INITIAL_VALUE`, in a const environment the panic is a
+            /// compilation error.
             #[allow(dead_code)]
             #[inline]
             pub const fn const_create_lower(value: $ty) -> $name {
-                $crate::_const_fn_assert::cfn_assert!(value <= $name::initial_value());
-                $name(value)
+                match $name::const_try_create_lower(value) {
+                    Some(id) => id,
+                    None => panic!("the value of an id created with `const_create_lower` must be less than or equal to `INITIAL_VALUE`"),
+                }
+            }
+
+            /// Const version of [`try_create_lower`](#method.try_create_lower),
+            /// return `None` if `value > INITIAL_VALUE`.
+            #[allow(dead_code)]
+            #[inline]
+            pub const fn const_try_create_lower(value: $ty) -> Option<$name> {
+                if value <= $name::INITIAL_VALUE {
+                    Some($name(value))
+                } else {
+                    None
+                }
             }
 
             /// Return a copy of the id if the value is inferior or equal as `initial_value`,
@@ -259,7 +696,79 @@ macro_rules! numid {
             }
         }
 
-        /// Increment the "current value" and create a new id with value = `current_value()`.
+        impl $crate::NumId for $name {
+            type Raw = $ty;
+
+            const INITIAL_VALUE: $ty = $name::INITIAL_VALUE;
+
+            const MAX_VALUE: $ty = $name::MAX_VALUE;
+
+            const OVERFLOW: $crate::Overflow = $name::OVERFLOW;
+
+            const STEP: $ty = $name::STEP;
+
+            #[inline]
+            fn new() -> $name {
+                $name::new()
+            }
+
+            #[inline]
+            fn try_new() -> Result<$name, $crate::Error> {
+                $name::try_new()
+            }
+
+            #[inline]
+            fn value(self) -> $ty {
+                self.0
+            }
+
+            #[inline]
+            fn current_value() -> $ty {
+                $name::current_value()
+            }
+
+            #[inline]
+            fn replace_current_value(value: $ty) -> bool {
+                $name::replace_current_value(value)
+            }
+
+            #[inline]
+            fn snapshot() -> $crate::CounterSnapshot<$ty> {
+                $name::snapshot()
+            }
+
+            #[inline]
+            fn restore(snapshot: $crate::CounterSnapshot<$ty>) -> bool {
+                $name::restore(snapshot)
+            }
+
+            #[inline]
+            fn create_maybe(value: $ty) -> Option<$name> {
+                $name::create_maybe(value)
+            }
+
+            #[inline]
+            fn create_lower(value: $ty) -> $name {
+                $name::create_lower(value)
+            }
+
+            #[inline]
+            fn try_create_lower(value: $ty) -> Result<$name, $crate::Error> {
+                $name::try_create_lower(value)
+            }
+
+            #[inline]
+            fn reproduce(self) -> $name {
+                $name::reproduce(self)
+            }
+
+            #[inline]
+            fn __from_raw(value: $ty) -> $name {
+                $name(value)
+            }
+        }
+
+        /// Increase the "current value" by `STEP` and create a new id with value = `current_value()`.
         /// This is equivalent to `new()`.
         impl Default for $name {
             #[inline]
@@ -268,16 +777,141 @@ macro_rules! numid {
             }
         }
 
-        $crate::__macro_attr_numid!($name $($mattr)*);
+        $crate::__macro_attr_numid!($name($ty) $($mattr [$($marg)*])*);
+        $crate::__source_numid!($name($ty) $($mattr [$($marg)*])*);
+        $crate::__next_numid!($name($ty) $($mattr [$($marg)*])*);
+        $crate::__clone_numid!($name $($mattr)*);
         $crate::__fmt_impl_numid!($name : Binary, Octal, LowerHex, UpperHex);
-        $crate::__display_numid!($name);
+        $crate::__display_numid!(__fmt_display_numid $name);
+        $crate::__from_str_numid!($name($ty) $($mattr)*);
+        $crate::__encoding_numid!($name($ty));
+        $crate::__obfuscate_numid!($name($ty));
+        $crate::__serde_numid!($name($ty) $($mattr)*);
+    };
+    ($(#[$attr:meta])* @$($rest:tt)+) => {
+        $crate::numid!{@__options [$(#[$attr])*] [] @$($rest)+}
     };
 }
 
 #[macro_export]
 #[doc(hidden)]
 macro_rules! __macro_attr_numid {
-    ($name:ident) => {};
+    ($name:ident($ty:ty)) => {};
+    ($name:ident($ty:ty) CloneIsClone [] $($stack:tt)*) => {
+        $crate::__macro_attr_numid!($name($ty) $($stack)*);
+    };
+    ($name:ident($ty:ty) CloneIsNew [] $($stack:tt)*) => {
+        $crate::__macro_attr_numid!($name($ty) $($stack)*);
+    };
+    ($name:ident($ty:ty) CloneIsReproduce [] $($stack:tt)*) => {
+        $crate::__macro_attr_numid!($name($ty) $($stack)*);
+    };
+    ($name:ident($ty:ty) OverflowPanic [] $($stack:tt)*) => {
+        $crate::__macro_attr_numid!($name($ty) $($stack)*);
+    };
+    ($name:ident($ty:ty) OverflowSaturate [] $($stack:tt)*) => {
+        $crate::__macro_attr_numid!($name($ty) $($stack)*);
+    };
+    ($name:ident($ty:ty) OverflowWrap [] $($stack:tt)*) => {
+        $crate::__macro_attr_numid!($name($ty) $($stack)*);
+    };
+    ($name:ident($ty:ty) SerdeReplaceCurrent [] $($stack:tt)*) => {
+        $crate::__macro_attr_numid!($name($ty) $($stack)*);
+    };
+    ($name:ident($ty:ty) SerdeAsString [] $($stack:tt)*) => {
+        $crate::__macro_attr_numid!($name($ty) $($stack)*);
+    };
+    ($name:ident($ty:ty) ParseAny [] $($stack:tt)*) => {
+        $crate::__macro_attr_numid!($name($ty) $($stack)*);
+    };
+    ($name:ident($ty:ty) ParseLower [] $($stack:tt)*) => {
+        $crate::__macro_attr_numid!($name($ty) $($stack)*);
+    };
+    ($name:ident($ty:ty) ParseCreate [] $($stack:tt)*) => {
+        $crate::__macro_attr_numid!($name($ty) $($stack)*);
+    };
+    ($name:ident($ty:ty) Prefix [$prefix:literal] $($stack:tt)*) => {
+        $crate::__macro_attr_numid!($name($ty) $($stack)*);
+    };
+    ($name:ident($ty:ty) Prefix [$($arg:tt)*] $($stack:tt)*) => {
+        compile_error!("the `@Prefix` built-in attribute expects a string literal : `@Prefix(\"usr_\")`");
+    };
+    ($name:ident($ty:ty) Pool [] $($stack:tt)*) => {
+        $crate::__pool_numid!($name($ty));
+        $crate::__macro_attr_numid!($name($ty) $($stack)*);
+    };
+    ($name:ident($ty:ty) ThreadCached [$size:expr] $($stack:tt)*) => {
+        const _: () = assert!($size > 0, "the block size of `@ThreadCached` must be positive");
+        $crate::__macro_attr_numid!($name($ty) $($stack)*);
+    };
+    ($name:ident($ty:ty) Step [$step:expr] $($stack:tt)*) => {
+        const _: () = assert!($name::STEP > 0, "the step of an id must be positive");
+        $crate::__macro_attr_numid!($name($ty) $($stack)*);
+    };
+    ($name:ident($ty:ty) Source [$source:path] $($stack:tt)*) => {
+        $crate::__macro_attr_numid!($name($ty) $($stack)*);
+    };
+    ($name:ident($ty:ty) Source [$($arg:tt)*] $($stack:tt)*) => {
+        compile_error!("the `@Source` built-in attribute expects the path of a static : `@Source(path::to::SOURCE)`");
+    };
+    ($name:ident($ty:ty) Step [$($arg:tt)*] $($stack:tt)*) => {
+        compile_error!("the `@Step` built-in attribute expects a step : `@Step(N)`");
+    };
+    ($name:ident($ty:ty) ThreadCached [$($arg:tt)*] $($stack:tt)*) => {
+        compile_error!("the `@ThreadCached` built-in attribute expects a block size : `@ThreadCached(N)`");
+    };
+    ($name:ident($ty:ty) $unknown:ident [] $($stack:tt)*) => {
+        compile_error!(concat!("unknown numid built-in attribute `@", stringify!($unknown), "`"));
+    };
+    ($name:ident($ty:ty) $unknown:ident [$($arg:tt)*] $($stack:tt)*) => {
+        compile_error!(concat!(
+            "unknown numid built-in attribute `@", stringify!($unknown), "(", stringify!($($arg)*), ")`"
+        ));
+    };
+}
+
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __next_numid {
+    ($name:ident($ty:ty)) => {
+        impl $name {
+            #[doc(hidden)]
+            #[inline]
+            fn __try_next() -> Result<$ty, $crate::Error> {
+                $crate::IdSource::next($name::__source(), $name::OVERFLOW, $name::INITIAL_VALUE, $name::STEP)
+            }
+        }
+    };
+    ($name:ident($ty:ty) ThreadCached [$size:expr] $($stack:tt)*) => {
+        $crate::__thread_cached_numid!($name($ty) $size);
+    };
+    ($name:ident($ty:ty) $other:ident [$($arg:tt)*] $($stack:tt)*) => {
+        $crate::__next_numid!($name($ty) $($stack)*);
+    };
+}
+
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __doc_numid {
+    () => { "A numerical id generated with the `numid!` macro." };
+    (ThreadCached $($stack:ident)*) => {
+        concat!(
+            "A numerical id generated with the `numid!` macro.\n\n",
+            "The ids are created from blocks of values reserved by each thread (`@ThreadCached`) : ",
+            "they are unique, but they are only ordered inside a thread. ",
+            "An id created after another one in a different thread can have a lower value, ",
+            "and the \"current value\" includes the values reserved and not yet used by the threads."
+        )
+    };
+    ($other:ident $($stack:ident)*) => { $crate::__doc_numid!($($stack)*) };
+}
+
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __clone_numid {
+    ($name:ident) => {
+        $crate::__clone_numid!($name CloneIsClone);
+    };
     ($name:ident CloneIsClone $($stack:ident)*) => {
         impl Clone for $name {
             #[inline]
@@ -287,10 +921,9 @@ macro_rules! __macro_attr_numid {
         }
 
         impl Copy for $name { }
-
-        $crate::__macro_attr_numid!($name $($stack)*);
     };
     ($name:ident CloneIsNew $($stack:ident)*) => {
+        #[allow(clippy::non_canonical_clone_impl)]
         impl Clone for $name {
             #[inline]
             fn clone(&self) -> $name {
@@ -299,10 +932,9 @@ macro_rules! __macro_attr_numid {
         }
 
         impl Copy for $name { }
-
-        $crate::__macro_attr_numid!($name $($stack)*);
     };
     ($name:ident CloneIsReproduce $($stack:ident)*) => {
+        #[allow(clippy::non_canonical_clone_impl)]
         impl Clone for $name {
             #[inline]
             fn clone(&self) -> $name {
@@ -311,9 +943,36 @@ macro_rules! __macro_attr_numid {
         }
 
         impl Copy for $name { }
-
-        $crate::__macro_attr_numid!($name $($stack)*);
     };
+    ($name:ident $other:ident $($stack:ident)*) => {
+        $crate::__clone_numid!($name $($stack)*);
+    };
+}
+
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __overflow_numid {
+    () => { $crate::Overflow::Panic };
+    (OverflowPanic $($stack:ident)*) => { $crate::Overflow::Panic };
+    (OverflowSaturate $($stack:ident)*) => { $crate::Overflow::Saturate };
+    (OverflowWrap $($stack:ident)*) => { $crate::Overflow::Wrap };
+    ($other:ident $($stack:ident)*) => { $crate::__overflow_numid!($($stack)*) };
+}
+
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __step_numid {
+    () => { 1 };
+    (Step [$step:expr] $($stack:tt)*) => { $step };
+    ($other:ident [$($arg:tt)*] $($stack:tt)*) => { $crate::__step_numid!($($stack)*) };
+}
+
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __prefix_numid {
+    () => { "" };
+    (Prefix [$prefix:literal] $($stack:tt)*) => { $prefix };
+    ($other:ident [$($arg:tt)*] $($stack:tt)*) => { $crate::__prefix_numid!($($stack)*) };
 }
 
 #[macro_export]
@@ -330,12 +989,25 @@ macro_rules! __fmt_impl_numid {
     }
 }
 
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __fmt_display_numid {
+    ($name:ident : Display) => {
+        impl $crate::_core::fmt::Display for $name {
+            fn fmt(&self, f: &mut $crate::_core::fmt::Formatter<'_>) -> $crate::_core::fmt::Result {
+                f.write_str($name::PREFIX)?;
+                $crate::_core::fmt::Display::fmt(&self.0, f)
+            }
+        }
+    };
+}
+
 #[cfg(feature = "display")]
 #[macro_export]
 #[doc(hidden)]
 macro_rules! __display_numid {
-    ($name:ident) => {
-        $crate::__fmt_impl_numid!($name: Display);
+    ($fmt_impl:ident $name:ident) => {
+        $crate::$fmt_impl!($name: Display);
     };
 }
 
@@ -343,7 +1015,32 @@ macro_rules! __display_numid {
 #[macro_export]
 #[doc(hidden)]
 macro_rules! __display_numid {
-    ($name:ident) => {};
+    ($fmt_impl:ident $name:ident) => {};
+}
+
+#[cfg(not(feature = "alloc"))]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __pool_numid {
+    ($name:ident($ty:ty)) => {
+        compile_error!("the `@Pool` built-in attribute requires the `alloc` feature of numid");
+    };
+}
+
+#[cfg(not(feature = "std"))]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __thread_cached_numid {
+    ($name:ident($ty:ty) $size:expr) => {
+        compile_error!("the `@ThreadCached` built-in attribute requires the `std` feature of numid");
+    };
+}
+
+#[cfg(not(feature = "serde"))]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __serde_numid {
+    ($name:ident($ty:ty) $($mattr:ident)*) => {};
 }
 
 #[cfg(feature = "example")]
@@ -354,6 +1051,7 @@ pub mod example;
 mod example;
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
 
     numid!(struct Id);
@@ -415,6 +1113,88 @@ mod tests {
         assert_eq!(id2.value(), 151);
     }
 
+    #[test]
+    fn tests_try_new() {
+        numid!(struct Id(u8) -> 250);
+
+        assert_eq!(Id::MAX_VALUE, 255);
+        assert_eq!(Id::remaining(), 5);
+        for value in 251..=255 {
+            assert_eq!(Id::try_new().unwrap().value(), value);
+        }
+        assert_eq!(Id::remaining(), 0);
+        assert_eq!(Id::try_new(), Err(crate::Error::Exhausted));
+        assert_eq!(Id::current_value(), 255);
+    }
+
+    #[test]
+    fn tests_remaining_signed() {
+        numid!(struct Neg(i8) -> -128);
+        numid!(@Step(10) struct Tens(i16) -> -32768);
+
+        assert_eq!(Neg::remaining(), 255);
+        assert_eq!(Neg::new().value(), -127);
+        assert_eq!(Neg::remaining(), 254);
+        assert!(Neg::replace_current_value(127));
+        assert_eq!(Neg::remaining(), 0);
+        assert_eq!(Tens::remaining(), 6553);
+    }
+
+    #[test]
+    #[should_panic]
+    fn tests_overflow_panic() {
+        numid!(@OverflowPanic struct Id(u8) -> 255);
+        let _ = Id::new();
+    }
+
+    #[test]
+    fn tests_overflow_saturate() {
+        numid!(@OverflowSaturate @Step(2) struct Id(u8) -> 252);
+
+        assert_eq!(Id::new().value(), 254);
+        assert_eq!(Id::try_new(), Err(crate::Error::Exhausted));
+        assert_eq!(Id::current_value(), 255);
+        assert_eq!(Id::remaining(), 0);
+        assert_eq!(Id::try_new(), Err(crate::Error::Exhausted));
+        assert_eq!(Id::epoch(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn tests_overflow_saturate_panic() {
+        numid!(@OverflowSaturate struct Id(u8) -> 254);
+
+        assert_eq!(Id::new().value(), 255);
+        let _ = Id::new();
+    }
+
+    #[test]
+    fn tests_overflow_wrap() {
+        numid!(@OverflowWrap struct Id(u8) -> 253);
+
+        assert_eq!(Id::new().value(), 254);
+        assert_eq!(Id::new().value(), 255);
+        assert_eq!(Id::epoch(), 0);
+        assert_eq!(Id::new().value(), 254);
+        assert_eq!(Id::epoch(), 1);
+        assert_eq!(Id::try_new().unwrap().value(), 255);
+        assert_eq!(Id::try_new().unwrap().value(), 254);
+        assert_eq!(Id::epoch(), 2);
+
+        // the `Clone` trait stay implemented with only a non-clone built-in attribute
+        let id = Id::new();
+        assert_eq!(id, id.clone());
+    }
+
+    #[test]
+    fn tests_overflow_wrap_no_room() {
+        numid!(@OverflowWrap struct Id(u8) -> 255);
+
+        assert_eq!(Id::try_new(), Err(crate::Error::Exhausted));
+        assert_eq!(Id::current_value(), 255);
+        assert_eq!(Id::epoch(), 0);
+    }
+
     #[test]
     fn tests_create_lower() {
         let id = Id::create_lower(0);
@@ -452,6 +1232,30 @@ mod tests {
         let _ = IdWithInitVal::const_create_lower(101);
     }
 
+    #[test]
+    fn tests_try_create_lower() {
+        assert_eq!(IdWithInitVal::try_create_lower(100).unwrap().value(), 100);
+        assert_eq!(
+            IdWithInitVal::try_create_lower(101),
+            Err(crate::Error::AboveInitialValue)
+        );
+
+        const C0: Option<IdWithInitVal> = IdWithInitVal::const_try_create_lower(50);
+        const C1: Option<IdWithInitVal> = IdWithInitVal::const_try_create_lower(101);
+        assert_eq!(C0.unwrap().value(), 50);
+        assert_eq!(C1, None);
+    }
+
+    #[test]
+    fn tests_try_create() {
+        numid!(struct IdTry -> 1);
+
+        assert_eq!(IdTry::try_create(5).unwrap().value(), 5);
+        assert_eq!(IdTry::try_create(3), Err(crate::Error::NotGreaterThanCurrent));
+        assert_eq!(IdTry::try_create(5), Err(crate::Error::NotGreaterThanCurrent));
+        assert_eq!(IdTry::current_value(), 5);
+    }
+
     #[test]
     fn tests_create_maybe() {
         numid!(struct IdMaybe -> 1);
@@ -0,0 +1,56 @@
+//! The locks of the mutexes, and the exclusive locks of files with `flock` (only on unix).
+
+use std::sync::{Mutex, MutexGuard};
+
+#[cfg(unix)]
+pub(crate) use self::file::{try_lock, FileLock};
+
+/// Lock `mutex`, even if a thread panicked while holding it : the generators and the sources only
+/// modify their state once the file or the database has been written, so it is always valid.
+pub(crate) fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|error| error.into_inner())
+}
+
+#[cfg(unix)]
+mod file {
+    use std::fs::File;
+    use std::io;
+    use std::os::unix::io::AsRawFd;
+
+    /// Apply the `flock` `operation` to `file`, retrying if interrupted by a signal.
+    fn flock(file: &File, operation: std::os::raw::c_int) -> io::Result<()> {
+        loop {
+            // SAFETY : `flock` only reads its arguments, the descriptor is owned by `file`.
+            if unsafe { libc::flock(file.as_raw_fd(), operation) } == 0 {
+                return Ok(());
+            }
+            let error = io::Error::last_os_error();
+            if error.kind() != io::ErrorKind::Interrupted {
+                return Err(error);
+            }
+        }
+    }
+
+    /// An exclusive `flock` on a file, released when dropped.
+    pub(crate) struct FileLock<'a>(&'a File);
+
+    impl<'a> FileLock<'a> {
+        /// Lock `file`, waiting until the other holders of the lock release it.
+        pub(crate) fn new(file: &'a File) -> io::Result<FileLock<'a>> {
+            flock(file, libc::LOCK_EX)?;
+            Ok(FileLock(file))
+        }
+    }
+
+    impl Drop for FileLock<'_> {
+        fn drop(&mut self) {
+            let _ = flock(self.0, libc::LOCK_UN);
+        }
+    }
+
+    /// Lock `file` until it is closed, without waiting : the error kind is `WouldBlock` if the
+    /// lock is held by another file description (of this process or another).
+    pub(crate) fn try_lock(file: &File) -> io::Result<()> {
+        flock(file, libc::LOCK_EX | libc::LOCK_NB)
+    }
+}
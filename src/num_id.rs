@@ -0,0 +1,89 @@
+//! The trait implemented by every id generated by `numid!`.
+
+use core::fmt;
+use core::hash::Hash;
+
+use crate::counter::{CounterSnapshot, Overflow};
+use crate::error::Error;
+use crate::primitive::Primitive;
+
+/// The methods and constants of the ids generated by `numid!`, for writing generic code over
+/// any id type.
+///
+/// This trait is implemented by the `numid!` macro, see [`example::NumId`](example/struct.NumId.html)
+/// for the documentation of each method.
+///
+/// # Examples
+/// ```
+/// use numid::{numid, NumId};
+///
+/// fn values<I: NumId>(ids: &[I]) -> Vec<I::Raw> {
+///     ids.iter().map(|id| id.value()).collect()
+/// }
+///
+/// numid!(struct UserId(u32));
+/// numid!(struct OrderId -> 100);
+///
+/// assert_eq!(values(&[UserId::new(), UserId::new()]), vec![1, 2]);
+/// assert_eq!(values(&[OrderId::new()]), vec![101]);
+/// ```
+pub trait NumId: Copy + Eq + Ord + Hash + fmt::Debug {
+    /// The integer type of the value of the id.
+    type Raw: Primitive;
+
+    /// Constant defined when calling the `numid!` macro (0 if not defined).
+    const INITIAL_VALUE: Self::Raw;
+
+    /// The largest value an id can have.
+    const MAX_VALUE: Self::Raw;
+
+    /// The behavior of `new()` and `try_new()` once the "current value" reached `MAX_VALUE`.
+    const OVERFLOW: Overflow;
+
+    /// The difference between the values of two successive ids created with `new()`.
+    const STEP: Self::Raw;
+
+    /// Increase the "current value" by `STEP` and create a new id with value = `current_value()`.
+    fn new() -> Self;
+
+    /// Increase the "current value" by `STEP` and create a new id with value = `current_value()`,
+    /// return an error if the id space is exhausted.
+    fn try_new() -> Result<Self, Error>;
+
+    /// Get the value of the id.
+    fn value(self) -> Self::Raw;
+
+    /// Return the "current value", the value of the last id created.
+    fn current_value() -> Self::Raw;
+
+    /// Replace the "current value" by the `value` parameter if it superior.
+    fn replace_current_value(value: Self::Raw) -> bool;
+
+    /// Return the "current value" and the epoch.
+    fn snapshot() -> CounterSnapshot<Self::Raw>;
+
+    /// Move the "current value" and the epoch forward to `snapshot` if it is ahead of them.
+    fn restore(snapshot: CounterSnapshot<Self::Raw>) -> bool;
+
+    /// Return Some id with specified value and replace the "current value" if
+    /// `replace_current_value(value)` is `true`.
+    fn create_maybe(value: Self::Raw) -> Option<Self>;
+
+    /// Create a id with a precised value, don't increment the "current value".
+    ///
+    /// # Panics
+    /// panic if `value > INITIAL_VALUE`
+    fn create_lower(value: Self::Raw) -> Self;
+
+    /// Create a id with a precised value, don't increment the "current value".
+    /// Return `Err(Error::AboveInitialValue)` if `value > INITIAL_VALUE`.
+    fn try_create_lower(value: Self::Raw) -> Result<Self, Error>;
+
+    /// Return a copy of the id if the value is inferior or equal as `INITIAL_VALUE`,
+    /// else return a new id and update the "current value".
+    fn reproduce(self) -> Self;
+
+    /// Create an id with any value, without checking nor modifying the "current value".
+    #[doc(hidden)]
+    fn __from_raw(value: Self::Raw) -> Self;
+}
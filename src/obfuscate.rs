@@ -0,0 +1,85 @@
+//! Reversible obfuscation of the values of the ids, with a keyed Feistel network.
+
+use crate::primitive::Primitive;
+
+/// Number of rounds of the Feistel network.
+const ROUNDS: u32 = 6;
+
+/// The round function of the Feistel network : a keyed mix of a half of the value,
+/// truncated to `mask`.
+#[inline]
+fn round(half: u64, key: u64, round: u32, mask: u64) -> u64 {
+    // The finalizer of SplitMix64.
+    let mut x =
+        half ^ key.rotate_left(round * 11) ^ u64::from(round).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    (x ^ (x >> 31)) & mask
+}
+
+/// Return the bits of the halves of `value` and the mask of a half.
+#[inline]
+fn split<T: Primitive>(value: T) -> (u64, u64, u64) {
+    let half_bits = T::BITS / 2;
+    let mask = u64::MAX >> (64 - half_bits);
+    let bits = value.to_bits();
+    ((bits >> half_bits) as u64 & mask, bits as u64 & mask, mask)
+}
+
+#[inline]
+fn join<T: Primitive>(left: u64, right: u64) -> T {
+    T::from_bits(u128::from(left) << (T::BITS / 2) | u128::from(right))
+}
+
+/// Permute the bits of `value` with a Feistel network keyed by `key`, the permutation is a
+/// bijection over all the values of `T` and is the same on every platform.
+#[doc(hidden)]
+pub fn obfuscate<T: Primitive>(value: T, key: u64) -> T {
+    let (mut left, mut right, mask) = split(value);
+    for r in 0..ROUNDS {
+        let next = left ^ round(right, key, r, mask);
+        left = right;
+        right = next;
+    }
+    join(left, right)
+}
+
+/// The inverse of [`obfuscate`](fn.obfuscate.html) with the same `key`.
+#[doc(hidden)]
+pub fn deobfuscate<T: Primitive>(value: T, key: u64) -> T {
+    let (mut left, mut right, mask) = split(value);
+    for r in (0..ROUNDS).rev() {
+        let previous = right ^ round(left, key, r, mask);
+        right = left;
+        left = previous;
+    }
+    join(left, right)
+}
+
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __obfuscate_numid {
+    ($name:ident($ty:ty)) => {
+        impl $name {
+            /// Return the value of the id permuted by a keyed Feistel network, the value looks random
+            /// but two ids never have the same obfuscated value for the same `key`.
+            /// The original id is found with [`deobfuscate`](#method.deobfuscate).
+            ///
+            /// This hides the number of ids created, it is not an encryption.
+            #[allow(dead_code)]
+            #[inline]
+            pub fn obfuscate(self, key: u64) -> $ty {
+                $crate::__obfuscate(self.0, key)
+            }
+
+            /// Return the id of a value obfuscated with [`obfuscate`](#method.obfuscate) with the
+            /// same `key`, the values accepted are the same as with `FromStr`. The "current value"
+            /// is not modified, even with `@ParseCreate`.
+            #[allow(dead_code)]
+            #[inline]
+            pub fn deobfuscate(value: $ty, key: u64) -> Result<$name, $crate::ParseError> {
+                $name::__from_decoded($crate::__deobfuscate(value, key))
+            }
+        }
+    };
+}
@@ -0,0 +1,140 @@
+//! Parsing of the ids generated by `numid!` from strings, with the `FromStr` and `TryFrom<&str>` traits.
+
+use core::fmt;
+use core::num::{IntErrorKind, ParseIntError};
+
+use crate::error::Error;
+use crate::primitive::Primitive;
+
+/// The error type of the `FromStr` and `TryFrom<&str>` implementations generated by `numid!`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ParseError {
+    /// The string doesn't contain any digit.
+    Empty,
+    /// The string contains a character which is not a digit of the radix.
+    InvalidDigit,
+    /// The number is out of the range of the type of the value.
+    OutOfRange,
+    /// The string doesn't start with the prefix of the id, defined with `@Prefix`.
+    WrongPrefix,
+    /// The value is valid but the id is refused by `@ParseLower` or `@ParseCreate`
+    /// (`Error::AboveInitialValue` or `Error::NotGreaterThanCurrent`).
+    Rejected(Error),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Empty => f.write_str("cannot parse an id from an empty string"),
+            ParseError::InvalidDigit => f.write_str("invalid digit found in the id"),
+            ParseError::OutOfRange => f.write_str("the value of the id is out of range"),
+            ParseError::WrongPrefix => f.write_str("the id doesn't start with the expected prefix"),
+            ParseError::Rejected(error) => fmt::Display::fmt(error, f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+impl From<ParseIntError> for ParseError {
+    fn from(error: ParseIntError) -> ParseError {
+        match error.kind() {
+            IntErrorKind::Empty => ParseError::Empty,
+            IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => ParseError::OutOfRange,
+            _ => ParseError::InvalidDigit,
+        }
+    }
+}
+
+/// Parse a value written in decimal, or in hexadecimal, octal or binary with the `0x`, `0o` or `0b` prefix.
+/// With a prefix, the digits are the bits of the value (in two's complement for the signed types),
+/// like the output of the `{:#x}`, `{:#o}` and `{:#b}` formats.
+#[doc(hidden)]
+pub fn parse_value<T: Primitive>(src: &str) -> Result<T, ParseError> {
+    let (radix, digits) = match src.get(..2) {
+        Some("0x") => (16, &src[2..]),
+        Some("0o") => (8, &src[2..]),
+        Some("0b") => (2, &src[2..]),
+        _ => return T::from_str_radix(src, 10).map_err(ParseError::from),
+    };
+    // `u128::from_str_radix` accepts a sign.
+    if digits.starts_with('+') || digits.starts_with('-') {
+        return Err(ParseError::InvalidDigit);
+    }
+    let bits = u128::from_str_radix(digits, radix)?;
+    if T::BITS < 128 && bits >> T::BITS != 0 {
+        return Err(ParseError::OutOfRange);
+    }
+    Ok(T::from_bits(bits))
+}
+
+/// Remove the `prefix` of an id, return `Err(ParseError::WrongPrefix)` if `src` doesn't start with it.
+#[doc(hidden)]
+#[inline]
+pub fn strip_prefix<'a>(src: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+    src.strip_prefix(prefix).ok_or(ParseError::WrongPrefix)
+}
+
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __from_str_numid {
+    ($name:ident($ty:ty)) => {
+        $crate::__from_str_numid!($name($ty) ParseAny);
+    };
+    ($name:ident($ty:ty) ParseAny $($stack:ident)*) => {
+        $crate::__from_str_numid!(@impl $name($ty) value => Ok($name(value)), Ok($name(value)));
+    };
+    ($name:ident($ty:ty) ParseLower $($stack:ident)*) => {
+        $crate::__from_str_numid!(
+            @impl $name($ty) value => $name::try_create_lower(value), $name::try_create_lower(value)
+        );
+    };
+    ($name:ident($ty:ty) ParseCreate $($stack:ident)*) => {
+        $crate::__from_str_numid!(@impl $name($ty) value => $name::try_create(value), Ok($name(value)));
+    };
+    (@impl $name:ident($ty:ty) $value:ident => $create:expr, $decode:expr) => {
+        impl $name {
+            /// Create the id of a parsed value, following the `@ParseAny`, `@ParseLower` or `@ParseCreate`
+            /// built-in attribute.
+            #[doc(hidden)]
+            #[inline]
+            fn __from_parsed($value: $ty) -> Result<$name, $crate::ParseError> {
+                $create.map_err($crate::ParseError::Rejected)
+            }
+
+            /// Return the id of a decoded value (`from_base62`, `deobfuscate`...) : the id already exists,
+            /// so the "current value" is never modified, even with `@ParseCreate`.
+            #[doc(hidden)]
+            #[inline]
+            #[allow(dead_code)]
+            fn __from_decoded($value: $ty) -> Result<$name, $crate::ParseError> {
+                $decode.map_err($crate::ParseError::Rejected)
+            }
+        }
+
+        /// Parse an id from its value written in decimal, or in hexadecimal, octal or binary with the
+        /// `0x`, `0o` or `0b` prefix, after the `PREFIX` of the id.
+        impl $crate::_core::str::FromStr for $name {
+            type Err = $crate::ParseError;
+
+            fn from_str(src: &str) -> Result<$name, $crate::ParseError> {
+                let src = $crate::__strip_prefix(src, $name::PREFIX)?;
+                $name::__from_parsed($crate::__parse_value(src)?)
+            }
+        }
+
+        impl<'a> $crate::_core::convert::TryFrom<&'a str> for $name {
+            type Error = $crate::ParseError;
+
+            #[inline]
+            fn try_from(src: &'a str) -> Result<$name, $crate::ParseError> {
+                src.parse()
+            }
+        }
+    };
+    ($name:ident($ty:ty) $other:ident $($stack:ident)*) => {
+        $crate::__from_str_numid!($name($ty) $($stack)*);
+    };
+}
@@ -0,0 +1,102 @@
+//! Persistence of the "current value" of the ids in a file, across the restarts of a process.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::num_id::NumId;
+use crate::primitive::Primitive;
+
+/// Write the "current value" of `I` (the highest value created) in decimal to the file `path`.
+///
+/// The value is written to a temporary file in the same directory, which is then renamed to `path` :
+/// the file always contains a complete value, even if the process stops during the call.
+///
+/// # Examples
+/// ```
+/// use numid::{load_high_water_mark, numid, save_high_water_mark};
+///
+/// numid!(struct MyId);
+///
+/// # let dir = std::env::temp_dir().join(format!("numid-doc-{}", std::process::id()));
+/// # std::fs::create_dir_all(&dir).unwrap();
+/// let path = dir.join("my_id");
+/// // At startup, before creating the first id.
+/// load_high_water_mark::<MyId>(&path).unwrap();
+/// let id = MyId::new();
+/// // Regularly, and before stopping.
+/// save_high_water_mark::<MyId>(&path).unwrap();
+/// assert_eq!(std::fs::read_to_string(&path).unwrap(), format!("{}\n", id.value()));
+/// # std::fs::remove_dir_all(&dir).unwrap();
+/// ```
+pub fn save_high_water_mark<I: NumId>(path: impl AsRef<Path>) -> io::Result<()> {
+    write_value(path.as_ref(), I::current_value())
+}
+
+/// Read a value written by [`save_high_water_mark`](fn.save_high_water_mark.html) and give it to
+/// `I::replace_current_value`, so the ids created after the call have greater values than the ids
+/// created before the save. Return true if the "current value" has been modified, false if the
+/// file doesn't exist.
+///
+/// The error kind is `InvalidData` if the file doesn't contain a value of type `I::Raw`.
+pub fn load_high_water_mark<I: NumId>(path: impl AsRef<Path>) -> io::Result<bool> {
+    Ok(match read_value(path.as_ref())? {
+        Some(value) => I::replace_current_value(value),
+        None => false,
+    })
+}
+
+/// The number of temporary files created by the process, to give each one a unique name.
+static TEMP_FILES: AtomicUsize = AtomicUsize::new(0);
+
+/// Write `value` in decimal to the file `path` atomically, through a temporary file renamed.
+pub(crate) fn write_value<T: Primitive>(path: &Path, value: T) -> io::Result<()> {
+    let name = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "the path has no file name"))?;
+    let mut temp_name = std::ffi::OsString::from(".");
+    temp_name.push(name);
+    temp_name.push(std::format!(
+        ".{}.{}.tmp",
+        std::process::id(),
+        TEMP_FILES.fetch_add(1, Ordering::Relaxed)
+    ));
+    let temp = path.with_file_name(temp_name);
+
+    // The file is new : a failure to create it never removes the file of another writer.
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&temp)?;
+    let result = writeln!(file, "{}", value).and_then(|_| file.sync_all());
+    drop(file);
+    if let Err(error) = result.and_then(|_| fs::rename(&temp, path)) {
+        let _ = fs::remove_file(&temp);
+        return Err(error);
+    }
+    #[cfg(unix)]
+    {
+        // Make the rename durable, this is best-effort.
+        let dir = path
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .unwrap_or_else(|| Path::new("."));
+        if let Ok(dir) = File::open(dir) {
+            let _ = dir.sync_all();
+        }
+    }
+    Ok(())
+}
+
+/// Read a value written by `write_value`, return `None` if the file doesn't exist.
+pub(crate) fn read_value<T: Primitive>(path: &Path) -> io::Result<Option<T>> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(error) => return Err(error),
+    };
+    T::from_str_radix(content.trim(), 10)
+        .map(Some)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+}
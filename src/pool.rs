@@ -0,0 +1,150 @@
+//! The free list of the ids generated with the `@Pool` built-in attribute.
+
+use alloc::collections::BTreeSet;
+
+use crate::counter::Overflow;
+use crate::error::Error;
+#[cfg(not(feature = "std"))]
+use crate::primitive::Locked;
+use crate::primitive::Primitive;
+use crate::source::IdSource;
+
+// The set allocates while the lock is held, a spin lock is only used without `std`.
+#[cfg(feature = "std")]
+type FreeList<T> = std::sync::Mutex<Option<BTreeSet<T>>>;
+#[cfg(not(feature = "std"))]
+type FreeList<T> = Locked<Option<BTreeSet<T>>>;
+
+/// A thread-safe set of freed values, reused before incrementing the "current value".
+///
+/// Every type generated by `numid!` with the `@Pool` built-in attribute owns a `static` pool.
+pub struct Pool<T> {
+    free: FreeList<T>,
+}
+
+impl<T: Primitive> Pool<T> {
+    /// Create an empty pool, usable in a `static`.
+    #[inline]
+    pub const fn new() -> Pool<T> {
+        Pool {
+            free: FreeList::new(None),
+        }
+    }
+
+    /// Lock the set of freed values and call `f` with it.
+    fn with<R>(&self, f: impl FnOnce(&mut Option<BTreeSet<T>>) -> R) -> R {
+        #[cfg(feature = "std")]
+        {
+            // The set is valid after a panic, it is only modified by `BTreeSet` methods.
+            f(&mut self.free.lock().unwrap_or_else(|error| error.into_inner()))
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            self.free.with(f)
+        }
+    }
+
+    /// Take the lowest freed value, or increase the "current value" of `counter` by `step`
+    /// if no value is free.
+    /// Return `Err(Error::Exhausted)` if no value is free and the counter is exhausted : the
+    /// counter never wraps, whatever the overflow policy of the type, because the values after
+    /// `initial` may still be allocated.
+    pub fn alloc<S: IdSource<T> + ?Sized>(
+        &self,
+        counter: &S,
+        initial: T,
+        step: T,
+    ) -> Result<T, Error> {
+        let reused = self.with(|free| {
+            let set = free.as_mut()?;
+            let lowest = *set.iter().next()?;
+            set.remove(&lowest);
+            Some(lowest)
+        });
+
+        match reused {
+            Some(value) => Ok(value),
+            None => counter.next(Overflow::Panic, initial, step),
+        }
+    }
+
+    /// Give `value` back to the pool.
+    /// Return false if `value` can't have been allocated (`value <= initial`, `value` greater
+    /// than the "current value" of `counter` or `value` not equal to `initial + k * step`) or is
+    /// already free.
+    pub fn free<S: IdSource<T> + ?Sized>(
+        &self,
+        counter: &S,
+        initial: T,
+        step: T,
+        value: T,
+    ) -> bool {
+        if value <= initial || value > counter.current() {
+            return false;
+        }
+        if value.wrapping_sub(initial).to_bits() % step.to_bits() != 0 {
+            return false;
+        }
+
+        self.with(|free| free.get_or_insert_with(BTreeSet::new).insert(value))
+    }
+
+    /// Return the number of freed values waiting to be reused.
+    pub fn free_count(&self) -> usize {
+        self.with(|free| free.as_ref().map_or(0, BTreeSet::len))
+    }
+}
+
+impl<T: Primitive> Default for Pool<T> {
+    fn default() -> Pool<T> {
+        Pool::new()
+    }
+}
+
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __pool_numid {
+    ($name:ident($ty:ty)) => {
+        impl $name {
+            #[doc(hidden)]
+            #[inline]
+            fn __pool() -> &'static $crate::Pool<$ty> {
+                static POOL: $crate::Pool<$ty> = $crate::Pool::new();
+                &POOL
+            }
+
+            /// Create an id with the lowest free value greater than `INITIAL_VALUE` : the lowest value
+            /// given back with `free()`, or a new value if no value has been freed.
+            /// Return `Err(Error::Exhausted)` if no value is free and the "current value"
+            /// reached `MAX_VALUE`, the overflow policy (`OVERFLOW`) is not applied.
+            #[allow(dead_code)]
+            pub fn alloc() -> Result<$name, $crate::Error> {
+                $name::__pool()
+                    .alloc($name::__source(), $name::INITIAL_VALUE, $name::STEP)
+                    .map($name)
+            }
+
+            /// Give the value of the id back, it will be reused by `alloc()`.
+            /// Return false if the value is already free or can't have been allocated.
+            ///
+            /// The pool doesn't know which ids are in use : only the ids created by `alloc()`
+            /// may be freed, an id created by `new()`, `create_maybe()` or `reserve()` and then
+            /// freed would be created again by `alloc()` while it is still held.
+            #[allow(dead_code)]
+            pub fn free(id: $name) -> bool {
+                $name::__pool().free(
+                    $name::__source(),
+                    $name::INITIAL_VALUE,
+                    $name::STEP,
+                    id.0,
+                )
+            }
+
+            /// Return the number of freed values waiting to be reused by `alloc()`.
+            #[allow(dead_code)]
+            pub fn free_count() -> usize {
+                $name::__pool().free_count()
+            }
+        }
+    };
+}
@@ -0,0 +1,307 @@
+//! Integer types usable as the value of an id generated by `numid!`.
+
+use core::cell::UnsafeCell;
+use core::convert::TryFrom;
+use core::fmt;
+use core::hash::Hash;
+use core::num::ParseIntError;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// An integer type which can be used as the value of an id.
+///
+/// This trait is sealed, it is implemented for all the primitive integer types
+/// (`u8` to `u128`, `i8` to `i128`, `usize` and `isize`).
+pub trait Primitive:
+    Copy + Eq + Ord + Hash + fmt::Debug + fmt::Display + Send + Sync + 'static + private::Sealed
+{
+    /// The storage used for the "current value" of the id,
+    /// an atomic type if the target provides one for this integer, a lock otherwise.
+    type Atomic: AtomicPrimitive<Self>;
+
+    /// The value `0`.
+    const ZERO: Self;
+
+    /// The value `1`.
+    const ONE: Self;
+
+    /// The largest value of the type.
+    const MAX: Self;
+
+    /// The size of the type in bits.
+    const BITS: u32;
+
+    /// Return `self + rhs`, wrapping around at the boundary of the type.
+    fn wrapping_add(self, rhs: Self) -> Self;
+
+    /// Return `self - rhs`, wrapping around at the boundary of the type.
+    fn wrapping_sub(self, rhs: Self) -> Self;
+
+    /// Return `Some(self + rhs)`, or `None` if overflow occurred.
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+
+    /// Return `Some(self - rhs)`, or `None` if overflow occurred.
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+
+    /// Convert `value` to this type, return `None` if it is out of range.
+    fn from_u128(value: u128) -> Option<Self>;
+
+    /// Convert `value` to this type, return `None` if it is out of range.
+    fn from_i128(value: i128) -> Option<Self>;
+
+    /// Convert `self` to `usize`, return `None` if it is out of range.
+    fn to_usize(self) -> Option<usize>;
+
+    /// Convert `value` to this type, return `None` if it is out of range.
+    fn from_usize(value: usize) -> Option<Self>;
+
+    /// Convert `value` to this type, truncating it (like `value as Self`).
+    fn wrapping_from_usize(value: usize) -> Self;
+
+    /// Return the bits of `self` (in two's complement for the signed types), zero-extended to `u128`.
+    fn to_bits(self) -> u128;
+
+    /// Create a value from its bits, the bits above the width of the type are ignored.
+    fn from_bits(bits: u128) -> Self;
+
+    /// Convert a string slice in a given base to an integer,
+    /// see `u32::from_str_radix` for the accepted format.
+    fn from_str_radix(src: &str, radix: u32) -> Result<Self, ParseIntError>;
+}
+
+/// A thread-safe cell holding a [`Primitive`](trait.Primitive.html) value.
+///
+/// Implemented by the `core::sync::atomic` integer types and by [`Locked`](struct.Locked.html).
+pub trait AtomicPrimitive<T>: Send + Sync {
+    /// Create a new cell containing `value`.
+    fn new(value: T) -> Self;
+
+    /// Load the value of the cell.
+    fn load(&self) -> T;
+
+    /// Add `value` to the value of the cell, wrapping around on overflow,
+    /// and return the previous value.
+    fn fetch_add(&self, value: T) -> T;
+
+    /// Store `new` in the cell if its value is `current`, the returned value is
+    /// the previous value of the cell and indicates whether the store happened.
+    /// Like `compare_exchange_weak`, this function is allowed to spuriously fail.
+    fn compare_exchange_weak(&self, current: T, new: T) -> Result<T, T>;
+}
+
+/// A value protected by a spin lock, used as storage for the integer types
+/// without native atomic support (like `u128`).
+pub struct Locked<T> {
+    lock: AtomicBool,
+    value: UnsafeCell<T>,
+}
+
+// Safety: the access to `value` is synchronized by `lock`.
+unsafe impl<T: Send> Sync for Locked<T> {}
+
+impl<T> Locked<T> {
+    /// Create a new unlocked value.
+    #[inline]
+    pub const fn new(value: T) -> Locked<T> {
+        Locked {
+            lock: AtomicBool::new(false),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    /// Lock the value and call `f` with a mutable reference to it.
+    pub fn with<R, F: FnOnce(&mut T) -> R>(&self, f: F) -> R {
+        while self
+            .lock
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            while self.lock.load(Ordering::Relaxed) {
+                core::hint::spin_loop();
+            }
+        }
+
+        struct Unlock<'a>(&'a AtomicBool);
+
+        impl Drop for Unlock<'_> {
+            fn drop(&mut self) {
+                self.0.store(false, Ordering::Release);
+            }
+        }
+
+        let _unlock = Unlock(&self.lock);
+        // Safety: the lock is held until `_unlock` is dropped.
+        f(unsafe { &mut *self.value.get() })
+    }
+}
+
+impl<T: Primitive> AtomicPrimitive<T> for Locked<T> {
+    #[inline]
+    fn new(value: T) -> Locked<T> {
+        Locked::new(value)
+    }
+
+    #[inline]
+    fn load(&self) -> T {
+        self.with(|v| *v)
+    }
+
+    #[inline]
+    fn fetch_add(&self, value: T) -> T {
+        self.with(|v| {
+            let previous = *v;
+            *v = previous.wrapping_add(value);
+            previous
+        })
+    }
+
+    #[inline]
+    fn compare_exchange_weak(&self, current: T, new: T) -> Result<T, T> {
+        self.with(|v| {
+            let previous = *v;
+            if previous == current {
+                *v = new;
+                Ok(previous)
+            } else {
+                Err(previous)
+            }
+        })
+    }
+}
+
+macro_rules! impl_atomic_primitive {
+    ($ty:ty, $atomic:ty) => {
+        impl AtomicPrimitive<$ty> for $atomic {
+            #[inline]
+            fn new(value: $ty) -> $atomic {
+                <$atomic>::new(value)
+            }
+
+            #[inline]
+            fn load(&self) -> $ty {
+                <$atomic>::load(self, Ordering::Acquire)
+            }
+
+            #[inline]
+            fn fetch_add(&self, value: $ty) -> $ty {
+                <$atomic>::fetch_add(self, value, Ordering::AcqRel)
+            }
+
+            #[inline]
+            fn compare_exchange_weak(&self, current: $ty, new: $ty) -> Result<$ty, $ty> {
+                <$atomic>::compare_exchange_weak(
+                    self,
+                    current,
+                    new,
+                    Ordering::AcqRel,
+                    Ordering::Acquire,
+                )
+            }
+        }
+    };
+}
+
+macro_rules! impl_primitive {
+    ($ty:ty as $unsigned:ty, $atomic:ty) => {
+        impl private::Sealed for $ty {}
+
+        impl Primitive for $ty {
+            type Atomic = $atomic;
+
+            const ZERO: $ty = 0;
+            const ONE: $ty = 1;
+            const MAX: $ty = <$ty>::MAX;
+            const BITS: u32 = <$ty>::BITS;
+
+            #[inline]
+            fn wrapping_add(self, rhs: $ty) -> $ty {
+                <$ty>::wrapping_add(self, rhs)
+            }
+
+            #[inline]
+            fn wrapping_sub(self, rhs: $ty) -> $ty {
+                <$ty>::wrapping_sub(self, rhs)
+            }
+
+            #[inline]
+            fn checked_add(self, rhs: $ty) -> Option<$ty> {
+                <$ty>::checked_add(self, rhs)
+            }
+
+            #[inline]
+            fn checked_sub(self, rhs: $ty) -> Option<$ty> {
+                <$ty>::checked_sub(self, rhs)
+            }
+
+            #[inline]
+            fn from_u128(value: u128) -> Option<$ty> {
+                <$ty>::try_from(value).ok()
+            }
+
+            #[inline]
+            fn from_i128(value: i128) -> Option<$ty> {
+                <$ty>::try_from(value).ok()
+            }
+
+            #[inline]
+            fn to_usize(self) -> Option<usize> {
+                usize::try_from(self).ok()
+            }
+
+            #[inline]
+            fn from_usize(value: usize) -> Option<$ty> {
+                <$ty>::try_from(value).ok()
+            }
+
+            #[inline]
+            fn wrapping_from_usize(value: usize) -> $ty {
+                value as $ty
+            }
+
+            #[inline]
+            fn to_bits(self) -> u128 {
+                self as $unsigned as u128
+            }
+
+            #[inline]
+            fn from_bits(bits: u128) -> $ty {
+                bits as $ty
+            }
+
+            #[inline]
+            fn from_str_radix(src: &str, radix: u32) -> Result<$ty, ParseIntError> {
+                <$ty>::from_str_radix(src, radix)
+            }
+        }
+    };
+    ($($ty:ty as $unsigned:ty => $atomic:ident if $width:literal),+) => {
+        $(
+            #[cfg(target_has_atomic = $width)]
+            impl_atomic_primitive!($ty, core::sync::atomic::$atomic);
+            #[cfg(target_has_atomic = $width)]
+            impl_primitive!($ty as $unsigned, core::sync::atomic::$atomic);
+            #[cfg(not(target_has_atomic = $width))]
+            impl_primitive!($ty as $unsigned, Locked<$ty>);
+        )+
+    };
+}
+
+impl_primitive! {
+    u8 as u8 => AtomicU8 if "8",
+    u16 as u16 => AtomicU16 if "16",
+    u32 as u32 => AtomicU32 if "32",
+    u64 as u64 => AtomicU64 if "64",
+    usize as usize => AtomicUsize if "ptr",
+    i8 as u8 => AtomicI8 if "8",
+    i16 as u16 => AtomicI16 if "16",
+    i32 as u32 => AtomicI32 if "32",
+    i64 as u64 => AtomicI64 if "64",
+    isize as usize => AtomicIsize if "ptr"
+}
+
+// No stable 128 bits atomic.
+impl_primitive!(u128 as u128, Locked<u128>);
+impl_primitive!(i128 as u128, Locked<i128>);
@@ -0,0 +1,175 @@
+//! Contiguous blocks of ids reserved with `reserve(n)`.
+
+use core::fmt;
+use core::iter::FusedIterator;
+
+use crate::num_id::NumId;
+use crate::primitive::Primitive;
+
+/// A contiguous block of fresh ids, returned by the `reserve(n)` method generated by `numid!`.
+/// The values of the ids are separated by the `STEP` of the id type (1 if not defined).
+///
+/// The "current value" is advanced once when the block is reserved, the ids of the block are then
+/// created without touching it. The block can be split for several workers with
+/// [`split_at`](#method.split_at) or [`chunks`](#method.chunks).
+///
+/// # Examples
+/// ```
+/// use numid::numid;
+///
+/// numid!(struct MyId -> 10);
+///
+/// let block = MyId::reserve(1000);
+/// assert_eq!(block.len(), 1000);
+/// assert_eq!(MyId::current_value(), 1010);
+///
+/// let (left, right) = block.split_at(500);
+/// assert_eq!(left.map(MyId::value).max(), Some(510));
+/// assert_eq!(right.map(MyId::value).min(), Some(511));
+/// ```
+pub struct IdRange<I: NumId> {
+    first: I::Raw,
+    len: usize,
+    step: I::Raw,
+}
+
+impl<I: NumId> IdRange<I> {
+    #[doc(hidden)]
+    #[inline]
+    pub fn __new(first: I::Raw, len: usize, step: I::Raw) -> IdRange<I> {
+        IdRange { first, len, step }
+    }
+
+    /// Return the number of ids left in the block.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Return true if there is no id left in the block.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Return true if the id is one of the ids left in the block.
+    pub fn contains(&self, id: I) -> bool {
+        if id.value() < self.first {
+            return false;
+        }
+        let offset = id.value().wrapping_sub(self.first).to_bits();
+        let step = self.step.to_bits();
+        offset % step == 0 && offset / step < self.len as u128
+    }
+
+    /// Divide the block in two at an index, the first block contains the `mid` first ids.
+    ///
+    /// # Panics
+    /// panic if `mid > len()`
+    pub fn split_at(self, mid: usize) -> (IdRange<I>, IdRange<I>) {
+        assert!(mid <= self.len, "`mid` is greater than the length of the block");
+        let second = IdRange::__new(self.offset(mid), self.len - mid, self.step);
+        (IdRange::__new(self.first, mid, self.step), second)
+    }
+
+    /// Return an iterator over blocks of `size` ids (the last block can be shorter).
+    ///
+    /// # Panics
+    /// panic if `size` is 0
+    pub fn chunks(self, size: usize) -> IdRangeChunks<I> {
+        assert!(size != 0, "chunk size must be non-zero");
+        IdRangeChunks { range: self, size }
+    }
+
+    #[inline]
+    fn offset(&self, offset: usize) -> I::Raw {
+        // The values of a block fit in `I::Raw`, except the past-the-end value of a block
+        // ending at `MAX` which wraps.
+        let delta = (offset as u128).wrapping_mul(self.step.to_bits());
+        self.first.wrapping_add(I::Raw::from_bits(delta))
+    }
+}
+
+impl<I: NumId> Clone for IdRange<I> {
+    fn clone(&self) -> IdRange<I> {
+        IdRange::__new(self.first, self.len, self.step)
+    }
+}
+
+impl<I: NumId> fmt::Debug for IdRange<I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IdRange")
+            .field("first", &self.first)
+            .field("len", &self.len)
+            .field("step", &self.step)
+            .finish()
+    }
+}
+
+impl<I: NumId> Iterator for IdRange<I> {
+    type Item = I;
+
+    #[inline]
+    fn next(&mut self) -> Option<I> {
+        if self.len == 0 {
+            return None;
+        }
+        let value = self.first;
+        self.first = self.first.wrapping_add(self.step);
+        self.len -= 1;
+        Some(I::__from_raw(value))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<I: NumId> DoubleEndedIterator for IdRange<I> {
+    #[inline]
+    fn next_back(&mut self) -> Option<I> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        Some(I::__from_raw(self.offset(self.len)))
+    }
+}
+
+impl<I: NumId> ExactSizeIterator for IdRange<I> {}
+
+impl<I: NumId> FusedIterator for IdRange<I> {}
+
+/// An iterator over sub-blocks of an [`IdRange`](struct.IdRange.html),
+/// returned by [`IdRange::chunks`](struct.IdRange.html#method.chunks).
+#[derive(Clone, Debug)]
+pub struct IdRangeChunks<I: NumId> {
+    range: IdRange<I>,
+    size: usize,
+}
+
+impl<I: NumId> Iterator for IdRangeChunks<I> {
+    type Item = IdRange<I>;
+
+    fn next(&mut self) -> Option<IdRange<I>> {
+        if self.range.is_empty() {
+            return None;
+        }
+        let mid = self.size.min(self.range.len());
+        let range = self.range.clone();
+        let (chunk, rest) = range.split_at(mid);
+        self.range = rest;
+        Some(chunk)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.range.len();
+        let count = len / self.size + usize::from(len % self.size != 0);
+        (count, Some(count))
+    }
+}
+
+impl<I: NumId> ExactSizeIterator for IdRangeChunks<I> {}
+
+impl<I: NumId> FusedIterator for IdRangeChunks<I> {}
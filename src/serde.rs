@@ -1 +1,105 @@
-#![cfg(feature = "serde")] 
+#![cfg(feature = "serde")]
+
+//! Implementation of the `Serialize` and `Deserialize` traits for the ids generated by `numid!`.
+
+use core::fmt;
+use core::marker::PhantomData;
+
+use serde::de::{self, Deserializer, Unexpected, Visitor};
+
+use crate::primitive::Primitive;
+
+struct StrOrIntVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Primitive> Visitor<'de> for StrOrIntVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("an integer or a string containing a decimal integer")
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<T, E> {
+        T::from_u128(u128::from(value))
+            .ok_or_else(|| E::invalid_value(Unexpected::Unsigned(value), &self))
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<T, E> {
+        T::from_i128(i128::from(value))
+            .ok_or_else(|| E::invalid_value(Unexpected::Signed(value), &self))
+    }
+
+    fn visit_u128<E: de::Error>(self, value: u128) -> Result<T, E> {
+        T::from_u128(value).ok_or_else(|| E::invalid_value(Unexpected::Other("u128"), &self))
+    }
+
+    fn visit_i128<E: de::Error>(self, value: i128) -> Result<T, E> {
+        T::from_i128(value).ok_or_else(|| E::invalid_value(Unexpected::Other("i128"), &self))
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<T, E> {
+        T::from_str_radix(value, 10).map_err(|_| E::invalid_value(Unexpected::Str(value), &self))
+    }
+}
+
+/// Deserialize an integer which can be represented as an integer or as a decimal string.
+/// The format must be self-describing (`Deserializer::deserialize_any` is used).
+#[doc(hidden)]
+pub fn deserialize_str_or_int<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: Primitive,
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_any(StrOrIntVisitor(PhantomData))
+}
+
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __serde_numid {
+    ($name:ident($ty:ty) $($mattr:ident)*) => {
+        impl $crate::_serde::Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: $crate::_serde::Serializer,
+            {
+                if $crate::__serde_as_string_numid!($($mattr)*) {
+                    serializer.collect_str(&self.0)
+                } else {
+                    $crate::_serde::Serialize::serialize(&self.0, serializer)
+                }
+            }
+        }
+
+        impl<'de> $crate::_serde::Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> Result<$name, D::Error>
+            where
+                D: $crate::_serde::Deserializer<'de>,
+            {
+                let value = if $crate::__serde_as_string_numid!($($mattr)*) {
+                    $crate::__deserialize_str_or_int::<$ty, D>(deserializer)?
+                } else {
+                    <$ty as $crate::_serde::Deserialize<'de>>::deserialize(deserializer)?
+                };
+                if $crate::__serde_replace_current_numid!($($mattr)*) {
+                    $name::replace_current_value(value);
+                }
+                Ok($name(value))
+            }
+        }
+    };
+}
+
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __serde_replace_current_numid {
+    () => { false };
+    (SerdeReplaceCurrent $($stack:ident)*) => { true };
+    ($other:ident $($stack:ident)*) => { $crate::__serde_replace_current_numid!($($stack)*) };
+}
+
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __serde_as_string_numid {
+    () => { false };
+    (SerdeAsString $($stack:ident)*) => { true };
+    ($other:ident $($stack:ident)*) => { $crate::__serde_as_string_numid!($($stack)*) };
+}
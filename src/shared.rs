@@ -0,0 +1,171 @@
+//! Id generators sharing their "current value" between processes, through a locked file.
+
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::marker::PhantomData;
+use std::os::unix::fs::FileExt;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+use crate::error::Error;
+use crate::lock::{self, FileLock};
+use crate::num_id::NumId;
+use crate::primitive::Primitive;
+use crate::range::IdRange;
+
+/// A generator of ids of type `I` whose "current value" is stored in a file shared by several
+/// processes (only on unix) : the processes using the same file create ids from the same sequence,
+/// unique and increasing.
+///
+/// Each id is created under an exclusive `flock` of the file, the value is read, increased by
+/// `STEP` and written back. The lock is held for a few microseconds, [`reserve`](#method.reserve)
+/// takes a block of ids at once for the processes creating many ids.
+///
+/// The file holds the bits of the "current value" (16 bytes, little-endian), an empty or new file
+/// starts at `INITIAL_VALUE`. The file is not synced to the disk, the sequence survives the crash
+/// of a process but not the crash of the system. The "current value" used by `I::new()` is not
+/// modified, and the overflow policy of `I` is not applied, see
+/// [Persistence](index.html#persistence).
+///
+/// # Examples
+/// ```
+/// use numid::{numid, SharedGenerator};
+///
+/// numid!(struct MyId);
+///
+/// # let dir = std::env::temp_dir().join(format!("numid-doc-shared-{}", std::process::id()));
+/// # std::fs::create_dir_all(&dir).unwrap();
+/// let path = dir.join("my_id");
+/// let worker1 = SharedGenerator::<MyId>::open(&path).unwrap();
+/// let worker2 = SharedGenerator::<MyId>::open(&path).unwrap();
+///
+/// assert_eq!(worker1.next_id().unwrap().value(), 1);
+/// assert_eq!(worker2.next_id().unwrap().value(), 2);
+/// assert_eq!(worker1.current_value().unwrap(), 2);
+/// # std::fs::remove_dir_all(&dir).unwrap();
+/// ```
+pub struct SharedGenerator<I: NumId> {
+    path: PathBuf,
+    // The `flock` doesn't exclude the threads using the same file description.
+    file: Mutex<File>,
+    id: PhantomData<fn() -> I>,
+}
+
+impl<I: NumId> SharedGenerator<I> {
+    /// Open the file `path` storing the "current value", the file is created if it doesn't exist.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<SharedGenerator<I>> {
+        let path = path.as_ref().to_path_buf();
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)?;
+        Ok(SharedGenerator {
+            path,
+            file: Mutex::new(file),
+            id: PhantomData,
+        })
+    }
+
+    fn file(&self) -> MutexGuard<'_, File> {
+        lock::lock(&self.file)
+    }
+
+    fn read(file: &File) -> io::Result<I::Raw> {
+        let mut bytes = [0; 16];
+        let len = file.read_at(&mut bytes, 0)?;
+        if len == 0 {
+            return Ok(I::INITIAL_VALUE);
+        }
+        if len != 16 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "the file doesn't contain a current value",
+            ));
+        }
+        let bits = u128::from_le_bytes(bytes);
+        let value = I::Raw::from_bits(bits);
+        if value.to_bits() != bits {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "the current value of the file is out of range",
+            ));
+        }
+        Ok(value.max(I::INITIAL_VALUE))
+    }
+
+    /// Lock the file and replace the "current value" by the result of `update`, return the
+    /// previous and the new "current value".
+    fn update(
+        &self,
+        update: impl FnOnce(I::Raw) -> Option<I::Raw>,
+    ) -> io::Result<(I::Raw, I::Raw)> {
+        let file = self.file();
+        let _lock = FileLock::new(&file)?;
+        let current = SharedGenerator::<I>::read(&file)?;
+        let next = match update(current) {
+            Some(next) => next,
+            None => return Err(io::Error::other(Error::Exhausted)),
+        };
+        // A single write of 16 bytes, never seen half done by the other processes.
+        file.write_all_at(&next.to_bits().to_le_bytes(), 0)?;
+        Ok((current, next))
+    }
+
+    /// Increase the shared "current value" by `STEP` and create a new id with this value.
+    ///
+    /// Return an error if the file can't be locked, read or written, or an error of kind `Other`
+    /// wrapping `Error::Exhausted` if the "current value" is `MAX_VALUE`.
+    pub fn next_id(&self) -> io::Result<I> {
+        let (_, next) = self.update(|current| current.checked_add(I::STEP))?;
+        Ok(I::__from_raw(next))
+    }
+
+    /// Increase the shared "current value" by `count * STEP` and return the block of the `count`
+    /// new ids, see `I::reserve()`. The errors are the same as [`next_id`](#method.next_id).
+    pub fn reserve(&self, count: usize) -> io::Result<IdRange<I>> {
+        let (current, _) = self.update(|current| {
+            let delta = (count as u128).checked_mul(I::STEP.to_bits())?;
+            if delta <= I::MAX_VALUE.wrapping_sub(current).to_bits() {
+                Some(current.wrapping_add(I::Raw::from_bits(delta)))
+            } else {
+                None
+            }
+        })?;
+        Ok(IdRange::__new(
+            current.wrapping_add(I::STEP),
+            count,
+            I::STEP,
+        ))
+    }
+
+    /// Return the shared "current value", the value of the last id created by any process.
+    pub fn current_value(&self) -> io::Result<I::Raw> {
+        let file = self.file();
+        let _lock = FileLock::new(&file)?;
+        SharedGenerator::<I>::read(&file)
+    }
+
+    /// Replace the shared "current value" by `value` if it superior.
+    /// Return true if the "current value" has been modified.
+    pub fn replace_current_value(&self, value: I::Raw) -> io::Result<bool> {
+        let (current, next) = self.update(|current| Some(current.max(value)))?;
+        Ok(next != current)
+    }
+
+    /// Return the path of the file storing the "current value".
+    #[inline]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl<I: NumId> fmt::Debug for SharedGenerator<I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharedGenerator")
+            .field("path", &self.path)
+            .finish()
+    }
+}
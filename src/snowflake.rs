@@ -0,0 +1,294 @@
+//! Time-ordered ids made of a timestamp, a node id and a sequence, generated with `numid!(@Snowflake(...) ...)`.
+
+use crate::error::Error;
+use crate::primitive::{AtomicPrimitive, Locked, Primitive};
+
+/// A source of time for the snowflake ids.
+pub trait Clock: Send + Sync {
+    /// Return the current time in milliseconds since an arbitrary epoch (the Unix epoch for
+    /// [`SystemClock`](struct.SystemClock.html)).
+    fn now_millis(&self) -> u64;
+}
+
+/// The clock of the system, the milliseconds since the Unix epoch. This is the default clock
+/// of the snowflake ids with the `std` feature.
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+    fn now_millis(&self) -> u64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |since| since.as_millis() as u64)
+    }
+}
+
+/// A clock which only moves when told to, for the tests.
+///
+/// ```
+/// use numid::{numid, MockClock};
+///
+/// static CLOCK: MockClock = MockClock::new(1000);
+///
+/// numid!(@Snowflake(node_bits = 10, seq_bits = 12) struct MyId);
+///
+/// MyId::set_clock(&CLOCK);
+/// assert_eq!(MyId::new().timestamp(), 1000);
+/// CLOCK.advance(5);
+/// assert_eq!(MyId::new().timestamp(), 1005);
+/// ```
+pub struct MockClock {
+    millis: <u64 as Primitive>::Atomic,
+}
+
+impl MockClock {
+    /// Create a clock at the time `millis`.
+    #[inline]
+    pub const fn new(millis: u64) -> MockClock {
+        MockClock {
+            millis: <<u64 as Primitive>::Atomic>::new(millis),
+        }
+    }
+
+    /// Set the time of the clock, it can go backwards.
+    pub fn set(&self, millis: u64) {
+        let mut current = AtomicPrimitive::load(&self.millis);
+        while let Err(previous) =
+            AtomicPrimitive::compare_exchange_weak(&self.millis, current, millis)
+        {
+            current = previous;
+        }
+    }
+
+    /// Move the clock forward by `millis`.
+    #[inline]
+    pub fn advance(&self, millis: u64) {
+        AtomicPrimitive::fetch_add(&self.millis, millis);
+    }
+}
+
+impl Clock for MockClock {
+    #[inline]
+    fn now_millis(&self) -> u64 {
+        AtomicPrimitive::load(&self.millis)
+    }
+}
+
+/// The state of a snowflake id type : the last id created, the node id and the clock.
+#[doc(hidden)]
+pub struct Snowflake {
+    last: <u64 as Primitive>::Atomic,
+    node: <u64 as Primitive>::Atomic,
+    clock: Locked<Option<&'static dyn Clock>>,
+}
+
+impl Snowflake {
+    /// Create the state of a snowflake id type, usable in a `static`.
+    #[inline]
+    pub const fn new() -> Snowflake {
+        Snowflake {
+            last: <<u64 as Primitive>::Atomic>::new(0),
+            node: <<u64 as Primitive>::Atomic>::new(0),
+            clock: Locked::new(None),
+        }
+    }
+
+    /// # Panics
+    /// panic if `node` doesn't fit in `node_bits` bits
+    pub fn set_node(&self, node: u64, node_bits: u32) {
+        assert!(
+            node >> node_bits == 0,
+            "the node id doesn't fit in {} bits",
+            node_bits
+        );
+        let mut current = AtomicPrimitive::load(&self.node);
+        while let Err(previous) = AtomicPrimitive::compare_exchange_weak(&self.node, current, node)
+        {
+            current = previous;
+        }
+    }
+
+    #[inline]
+    pub fn node(&self) -> u64 {
+        AtomicPrimitive::load(&self.node)
+    }
+
+    #[inline]
+    pub fn set_clock(&self, clock: &'static dyn Clock) {
+        self.clock.with(|current| *current = Some(clock));
+    }
+
+    fn now_millis(&self) -> Result<u64, Error> {
+        match self.clock.with(|clock| *clock) {
+            Some(clock) => Ok(clock.now_millis()),
+            #[cfg(feature = "std")]
+            None => Ok(SystemClock.now_millis()),
+            #[cfg(not(feature = "std"))]
+            None => Err(Error::NoClock),
+        }
+    }
+
+    /// Return a new id, greater than all the ids returned before.
+    ///
+    /// When the clock goes backwards, or when the sequence of the current millisecond is
+    /// exhausted, the timestamp of the last id is kept (or incremented), so the ids stay ordered.
+    pub fn try_next(&self, node_bits: u32, seq_bits: u32) -> Result<u64, Error> {
+        let shift = node_bits + seq_bits;
+        let seq_max = (1 << seq_bits) - 1;
+        let now = self.now_millis()?;
+        let node = self.node() << seq_bits;
+
+        let mut last = AtomicPrimitive::load(&self.last);
+        loop {
+            let last_timestamp = last >> shift;
+            let last_seq = last & seq_max;
+            let (timestamp, seq) = if now > last_timestamp {
+                (now, 0)
+            } else if last_seq < seq_max {
+                (last_timestamp, last_seq + 1)
+            } else {
+                (last_timestamp + 1, 0)
+            };
+            if timestamp.checked_shr(64 - shift).unwrap_or(0) != 0 {
+                return Err(Error::Exhausted);
+            }
+            let next = timestamp << shift | node | seq;
+            match AtomicPrimitive::compare_exchange_weak(&self.last, last, next) {
+                Ok(_) => return Ok(next),
+                Err(previous) => last = previous,
+            }
+        }
+    }
+}
+
+impl Default for Snowflake {
+    fn default() -> Snowflake {
+        Snowflake::new()
+    }
+}
+
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __snowflake_numid {
+    ($(#[$attr:meta])* $vis:vis struct $name:ident [$node_bits:expr, $seq_bits:expr]) => {
+        /// A snowflake id generated with the `numid!` macro.
+        #[warn(non_camel_case_types)]
+        #[warn(dead_code)] // rust-lang : issue 66030
+        #[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+        $(#[$attr])*
+        $vis struct $name(u64);
+
+        const _: () = assert!(
+            $name::NODE_BITS + $name::SEQ_BITS < 64,
+            "the node and sequence bits of a snowflake id must leave room for the timestamp"
+        );
+
+        impl $name {
+            /// The number of bits of the node id.
+            pub const NODE_BITS: u32 = $node_bits;
+
+            /// The number of bits of the sequence, the number of ids which can be created
+            /// in a millisecond is `2^SEQ_BITS`.
+            pub const SEQ_BITS: u32 = $seq_bits;
+
+            /// The number of bits of the timestamp.
+            pub const TIMESTAMP_BITS: u32 = 64 - $name::NODE_BITS - $name::SEQ_BITS;
+
+            #[doc(hidden)]
+            #[inline]
+            fn __snowflake() -> &'static $crate::__Snowflake {
+                static SNOWFLAKE: $crate::__Snowflake = $crate::__Snowflake::new();
+                &SNOWFLAKE
+            }
+
+            /// Create a new id from the time of the clock, the node id and a sequence.
+            /// The ids created are always increasing, even if the clock goes backwards.
+            ///
+            /// # Panics
+            /// panic if the timestamp doesn't fit in `TIMESTAMP_BITS` bits, or if no clock
+            /// has been set without the `std` feature.
+            #[allow(dead_code)]
+            #[inline]
+            pub fn new() -> $name {
+                match $name::try_new() {
+                    Ok(id) => id,
+                    Err(error) => panic!("{}", error),
+                }
+            }
+
+            /// Fallible version of [`new`](#method.new).
+            #[allow(dead_code)]
+            #[inline]
+            pub fn try_new() -> Result<$name, $crate::Error> {
+                $name::__snowflake()
+                    .try_next($name::NODE_BITS, $name::SEQ_BITS)
+                    .map($name)
+            }
+
+            /// Set the node id put in the ids created after this call, it should be set
+            /// before creating the first id.
+            ///
+            /// # Panics
+            /// panic if `node` doesn't fit in `NODE_BITS` bits
+            #[allow(dead_code)]
+            #[inline]
+            pub fn set_node(node: u64) {
+                $name::__snowflake().set_node(node, $name::NODE_BITS)
+            }
+
+            /// Set the clock used by `new()`, the default clock is `numid::SystemClock`
+            /// with the `std` feature.
+            #[allow(dead_code)]
+            #[inline]
+            pub fn set_clock(clock: &'static dyn $crate::Clock) {
+                $name::__snowflake().set_clock(clock)
+            }
+
+            /// Create an id from its parts, the parts are truncated to their number of bits.
+            #[allow(dead_code)]
+            #[inline]
+            pub const fn from_parts(timestamp: u64, node: u64, sequence: u64) -> $name {
+                let node_mask = (1 << $name::NODE_BITS) - 1;
+                let seq_mask = (1 << $name::SEQ_BITS) - 1;
+                $name(
+                    timestamp << ($name::NODE_BITS + $name::SEQ_BITS)
+                        | (node & node_mask) << $name::SEQ_BITS
+                        | sequence & seq_mask,
+                )
+            }
+
+            /// Get the value of the id.
+            #[allow(dead_code)]
+            #[inline]
+            pub const fn value(self) -> u64 {
+                self.0
+            }
+
+            /// Get the timestamp of the id, in milliseconds.
+            #[allow(dead_code)]
+            #[inline]
+            pub const fn timestamp(self) -> u64 {
+                self.0 >> ($name::NODE_BITS + $name::SEQ_BITS)
+            }
+
+            /// Get the node id of the id.
+            #[allow(dead_code)]
+            #[inline]
+            pub const fn node(self) -> u64 {
+                (self.0 >> $name::SEQ_BITS) & ((1 << $name::NODE_BITS) - 1)
+            }
+
+            /// Get the sequence of the id, inside its millisecond.
+            #[allow(dead_code)]
+            #[inline]
+            pub const fn sequence(self) -> u64 {
+                self.0 & ((1 << $name::SEQ_BITS) - 1)
+            }
+        }
+
+        $crate::__fmt_impl_numid!($name : Binary, Octal, LowerHex, UpperHex);
+        $crate::__display_numid!(__fmt_impl_numid $name);
+    };
+}
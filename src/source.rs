@@ -0,0 +1,175 @@
+//! The storage of the "current value" of an id type, pluggable with `@Source(path::to::SOURCE)`.
+
+use crate::counter::{Counter, CounterSnapshot, Overflow};
+use crate::error::Error;
+use crate::primitive::Primitive;
+
+/// A storage of the "current value" of an id type, the values of the ids created with `new()`,
+/// `try_new()` and `reserve()` come from the source.
+///
+/// A type generated by `numid!` uses a [`Counter`](struct.Counter.html) in a `static` by default,
+/// the `@Source(path::to::SOURCE)` built-in attribute (or `#[numid(source = path::to::SOURCE)]`
+/// with the derive) makes it use the `static` `SOURCE` instead, which can be any type implementing
+/// `IdSource` : a counter stored in a file or a database, a mock for the tests...
+///
+/// The source must start at the `INITIAL_VALUE` of the type, it receives the `OVERFLOW`,
+/// `INITIAL_VALUE` and `STEP` of the type with each request.
+///
+/// # Examples
+/// ```
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use numid::{numid, Counter, Error, IdSource, Overflow, Primitive};
+///
+/// /// A counter which counts the requests.
+/// struct CountingSource {
+///     counter: Counter<u64>,
+///     requests: AtomicUsize,
+/// }
+///
+/// impl IdSource<u64> for CountingSource {
+///     fn next(&self, overflow: Overflow, initial: u64, step: u64) -> Result<u64, Error> {
+///         self.requests.fetch_add(1, Ordering::Relaxed);
+///         self.counter.try_next(overflow, initial, step)
+///     }
+///
+///     fn reserve_block(
+///         &self,
+///         count: usize,
+///         overflow: Overflow,
+///         initial: u64,
+///         step: u64,
+///     ) -> Result<u64, Error> {
+///         self.requests.fetch_add(1, Ordering::Relaxed);
+///         self.counter.try_reserve(count, overflow, initial, step)
+///     }
+///
+///     fn observe_max(&self, value: u64) -> bool {
+///         self.counter.replace_current_value(value)
+///     }
+///
+///     fn current(&self) -> u64 {
+///         self.counter.current_value()
+///     }
+/// }
+///
+/// static SOURCE: CountingSource = CountingSource {
+///     counter: Counter::from_atomic(<<u64 as Primitive>::Atomic>::new(0)),
+///     requests: AtomicUsize::new(0),
+/// };
+///
+/// numid!(@Source(SOURCE) struct MyId);
+///
+/// assert_eq!(MyId::new().value(), 1);
+/// assert_eq!(MyId::reserve(10).len(), 10);
+/// assert_eq!(MyId::current_value(), 11);
+/// assert_eq!(SOURCE.requests.load(Ordering::Relaxed), 2);
+/// ```
+pub trait IdSource<T: Primitive>: Sync {
+    /// Increase the "current value" by `step` and return it, see
+    /// [`Counter::try_next`](struct.Counter.html#method.try_next) for the handling of `overflow`.
+    fn next(&self, overflow: Overflow, initial: T, step: T) -> Result<T, Error>;
+
+    /// Increase the "current value" by `count * step` and return the first value of the block
+    /// reserved, see [`Counter::try_reserve`](struct.Counter.html#method.try_reserve).
+    fn reserve_block(&self, count: usize, overflow: Overflow, initial: T, step: T)
+        -> Result<T, Error>;
+
+    /// Replace the "current value" by `value` if it superior, the values returned after the call
+    /// are greater than `value`. Return true if the "current value" has been modified.
+    fn observe_max(&self, value: T) -> bool;
+
+    /// Return the "current value", the last value returned.
+    fn current(&self) -> T;
+
+    /// Return the number of times the "current value" wrapped with `Overflow::Wrap`,
+    /// 0 by default.
+    fn epoch(&self) -> usize {
+        0
+    }
+
+    /// Return the "current value" and the epoch.
+    fn snapshot(&self) -> CounterSnapshot<T> {
+        CounterSnapshot {
+            current_value: self.current(),
+            epoch: self.epoch(),
+        }
+    }
+
+    /// Move the "current value" forward to `snapshot`, calls `observe_max` by default
+    /// (the epoch is ignored).
+    fn restore(&self, snapshot: CounterSnapshot<T>) -> bool {
+        self.observe_max(snapshot.current_value)
+    }
+}
+
+impl<T: Primitive> IdSource<T> for Counter<T> {
+    #[inline]
+    fn next(&self, overflow: Overflow, initial: T, step: T) -> Result<T, Error> {
+        self.try_next(overflow, initial, step)
+    }
+
+    #[inline]
+    fn reserve_block(
+        &self,
+        count: usize,
+        overflow: Overflow,
+        initial: T,
+        step: T,
+    ) -> Result<T, Error> {
+        self.try_reserve(count, overflow, initial, step)
+    }
+
+    #[inline]
+    fn observe_max(&self, value: T) -> bool {
+        self.replace_current_value(value)
+    }
+
+    #[inline]
+    fn current(&self) -> T {
+        self.current_value()
+    }
+
+    #[inline]
+    fn epoch(&self) -> usize {
+        Counter::epoch(self)
+    }
+
+    #[inline]
+    fn snapshot(&self) -> CounterSnapshot<T> {
+        Counter::snapshot(self)
+    }
+
+    #[inline]
+    fn restore(&self, snapshot: CounterSnapshot<T>) -> bool {
+        Counter::restore(self, snapshot)
+    }
+}
+
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __source_numid {
+    ($name:ident($ty:ty)) => {
+        impl $name {
+            #[doc(hidden)]
+            #[inline]
+            fn __source() -> &'static impl $crate::IdSource<$ty> {
+                static COUNTER: $crate::Counter<$ty> = $crate::Counter::from_atomic(
+                    <<$ty as $crate::Primitive>::Atomic>::new($name::INITIAL_VALUE)
+                );
+                &COUNTER
+            }
+        }
+    };
+    ($name:ident($ty:ty) Source [$source:path] $($stack:tt)*) => {
+        impl $name {
+            #[doc(hidden)]
+            #[inline]
+            fn __source() -> &'static impl $crate::IdSource<$ty> {
+                &$source
+            }
+        }
+    };
+    ($name:ident($ty:ty) $other:ident [$($arg:tt)*] $($stack:tt)*) => {
+        $crate::__source_numid!($name($ty) $($stack)*);
+    };
+}
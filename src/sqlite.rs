@@ -0,0 +1,304 @@
+//! An id source reserving blocks of values in a SQLite database, with the `rusqlite` feature.
+
+use std::fmt;
+use std::path::Path;
+use std::string::{String, ToString};
+use std::sync::{Mutex, MutexGuard};
+
+use rusqlite::types::Type;
+use rusqlite::{Connection, OptionalExtension, TransactionBehavior};
+
+use crate::counter::Overflow;
+use crate::error::Error;
+use crate::lock;
+use crate::primitive::Primitive;
+use crate::source::IdSource;
+
+/// The table storing the sequences, one row per source.
+const TABLE: &str = "numid_sequences";
+
+struct State<T> {
+    connection: Option<Connection>,
+    current: T,
+    ceiling: T,
+    error: Option<rusqlite::Error>,
+}
+
+/// An [`IdSource`](trait.IdSource.html) storing the "current value" of an id type in a SQLite
+/// database (with the `rusqlite` feature), so the ids are never reused across the runs of the
+/// program.
+///
+/// The source keeps one row named `name` in the table `numid_sequences`, holding the end of the
+/// block of values reserved (the "ceiling", as decimal text so that every `u128` fits). When its
+/// block is used up, the source reads the row, reserves `block` more values and writes the new
+/// ceiling in an immediate transaction, before handing the values to `new()`, `try_new()` and
+/// `reserve()`. The rest of the block is skipped after a restart, but no id is created twice,
+/// even if several connections (or processes) use the same database.
+///
+/// The source is declared in a `static` and given to the id type with `@Source(SOURCE)`, the
+/// connection is set at runtime with [`open`](#method.open) or
+/// [`set_connection`](#method.set_connection). Until then, and when the database fails,
+/// `try_new()` returns `Error::Unavailable` (`new()` panics and `replace_current_value()` returns
+/// false), the error of the database is kept for [`take_error`](#method.take_error).
+/// The overflow policy of the type is ignored (`@OverflowWrap` and `@OverflowSaturate` behave like
+/// the default), see [Persistence](index.html#persistence).
+///
+/// # Examples
+/// ```
+/// use numid::{numid, SqliteSource};
+///
+/// static SOURCE: SqliteSource<u64> = SqliteSource::new("my_id", 0, 100);
+///
+/// numid!(@Source(SOURCE) struct MyId);
+///
+/// SOURCE.set_connection(rusqlite::Connection::open_in_memory().unwrap()).unwrap();
+/// assert_eq!(MyId::new().value(), 1);
+/// assert_eq!(MyId::new().value(), 2);
+/// assert_eq!(SOURCE.ceiling(), 100);
+/// ```
+pub struct SqliteSource<T: Primitive> {
+    name: &'static str,
+    initial: T,
+    block: usize,
+    state: Mutex<State<T>>,
+}
+
+impl<T: Primitive> SqliteSource<T> {
+    /// Create a source of the sequence `name` starting at `initial` (the `INITIAL_VALUE` of the
+    /// id type) and reserving blocks of `block` values. The source has no connection.
+    ///
+    /// The source panics when it creates an id of a type whose `INITIAL_VALUE` is not `initial`,
+    /// its values could be the values of `create_lower`.
+    ///
+    /// # Panics
+    /// panic if `block` is 0
+    pub const fn new(name: &'static str, initial: T, block: usize) -> SqliteSource<T> {
+        assert!(block != 0, "block size must be non-zero");
+        SqliteSource {
+            name,
+            initial,
+            block,
+            state: Mutex::new(State {
+                connection: None,
+                current: initial,
+                ceiling: initial,
+                error: None,
+            }),
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, State<T>> {
+        lock::lock(&self.state)
+    }
+
+    /// Open the database `path` (created if it doesn't exist) and use it, see
+    /// [`set_connection`](#method.set_connection).
+    pub fn open(&self, path: impl AsRef<Path>) -> rusqlite::Result<()> {
+        self.set_connection(Connection::open(path)?)
+    }
+
+    /// Use the database of `connection`, the table `numid_sequences` is created if it doesn't
+    /// exist. The next values follow the ceiling recorded in the database, or the "current value"
+    /// of the source if it is greater.
+    pub fn set_connection(&self, connection: Connection) -> rusqlite::Result<()> {
+        connection.execute_batch(&std::format!(
+            "CREATE TABLE IF NOT EXISTS {} (name TEXT PRIMARY KEY NOT NULL, value TEXT NOT NULL)",
+            TABLE
+        ))?;
+        let stored = self.read(&connection)?;
+        let mut state = self.state();
+        if stored > state.current {
+            state.current = stored;
+        }
+        state.ceiling = state.current;
+        state.connection = Some(connection);
+        Ok(())
+    }
+
+    /// Remove the connection of the source and return it, the values left in the block are
+    /// skipped.
+    pub fn take_connection(&self) -> Option<Connection> {
+        let mut state = self.state();
+        state.ceiling = state.current;
+        state.connection.take()
+    }
+
+    /// Return the last error of the database, the cause of the last `Error::Unavailable`, and
+    /// forget it.
+    pub fn take_error(&self) -> Option<rusqlite::Error> {
+        self.state().error.take()
+    }
+
+    /// Return the value recorded in the row of the source, or `initial` if there is no row.
+    fn read(&self, connection: &Connection) -> rusqlite::Result<T> {
+        let value: Option<String> = connection
+            .query_row(
+                &std::format!("SELECT value FROM {} WHERE name = ?1", TABLE),
+                [self.name],
+                |row| row.get(0),
+            )
+            .optional()?;
+        match value {
+            Some(value) => T::from_str_radix(&value, 10)
+                .map(|value| value.max(self.initial))
+                .map_err(|error| {
+                    rusqlite::Error::FromSqlConversionFailure(
+                        0,
+                        Type::Text,
+                        std::boxed::Box::new(error),
+                    )
+                }),
+            None => Ok(self.initial),
+        }
+    }
+
+    /// Write the ceiling of a new block of at least `delta` values (a multiple of `step`) in a
+    /// transaction. The block continues the block of `state` if the database still records its
+    /// ceiling, otherwise it starts after the ceiling recorded. Return the start and the end of
+    /// the block, or `None` if `delta` doesn't fit.
+    fn write_block(
+        &self,
+        connection: &mut Connection,
+        (current, ceiling): (T, T),
+        delta: u128,
+        step: T,
+    ) -> rusqlite::Result<Option<(T, T)>> {
+        let transaction = connection.transaction_with_behavior(TransactionBehavior::Immediate)?;
+        let stored = self.read(&transaction)?;
+        let start = if stored == ceiling {
+            current
+        } else {
+            stored.max(current)
+        };
+        // `T::MAX - start` is the number of values above `start`.
+        let room = T::MAX.wrapping_sub(start).to_bits();
+        if delta > room {
+            return Ok(None);
+        }
+        let step = step.to_bits();
+        let size = (self.block as u128)
+            .checked_mul(step)
+            .map_or(room, |size| size.max(delta).min(room));
+        let ceiling = start.wrapping_add(T::from_bits(size - size % step));
+        transaction.execute(
+            &std::format!(
+                "INSERT OR REPLACE INTO {} (name, value) VALUES (?1, ?2)",
+                TABLE
+            ),
+            [self.name, ceiling.to_string().as_str()],
+        )?;
+        transaction.commit()?;
+        Ok(Some((start, ceiling)))
+    }
+
+    /// Replace the block of `state` by a new block of at least `delta` values.
+    fn reserve(&self, state: &mut State<T>, delta: u128, step: T) -> Result<(), Error> {
+        let block = (state.current, state.ceiling);
+        let connection = state.connection.as_mut().ok_or(Error::Unavailable)?;
+        match self.write_block(connection, block, delta, step) {
+            Ok(Some((start, ceiling))) => {
+                state.current = start;
+                state.ceiling = ceiling;
+                Ok(())
+            }
+            Ok(None) => Err(Error::Exhausted),
+            Err(error) => {
+                state.error = Some(error);
+                Err(Error::Unavailable)
+            }
+        }
+    }
+
+    /// Take `count` values from the block, reserving a new block if needed, and return the first.
+    fn take(&self, count: usize, step: T) -> Result<T, Error> {
+        let mut state = self.state();
+        let delta = (count as u128)
+            .checked_mul(step.to_bits())
+            .ok_or(Error::Exhausted)?;
+        if state.connection.is_none() || delta > state.ceiling.wrapping_sub(state.current).to_bits()
+        {
+            self.reserve(&mut state, delta, step)?;
+        }
+        let first = state.current.wrapping_add(step);
+        state.current = state.current.wrapping_add(T::from_bits(delta));
+        Ok(first)
+    }
+
+    /// Panic if `initial`, the `INITIAL_VALUE` of the id type, is not the start of the source.
+    fn check_initial(&self, initial: T) {
+        assert!(
+            initial == self.initial,
+            "the `SqliteSource` {:?} starts at {}, not at the INITIAL_VALUE {} of the id type",
+            self.name,
+            self.initial,
+            initial
+        );
+    }
+
+    /// Return the end of the block of values reserved, the value recorded in the database.
+    pub fn ceiling(&self) -> T {
+        self.state().ceiling
+    }
+
+    /// Return the name of the row of the source.
+    #[inline]
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Return the number of values of a block.
+    #[inline]
+    pub fn block(&self) -> usize {
+        self.block
+    }
+}
+
+impl<T: Primitive> IdSource<T> for SqliteSource<T> {
+    fn next(&self, _overflow: Overflow, initial: T, step: T) -> Result<T, Error> {
+        self.check_initial(initial);
+        self.take(1, step)
+    }
+
+    fn reserve_block(
+        &self,
+        count: usize,
+        _overflow: Overflow,
+        initial: T,
+        step: T,
+    ) -> Result<T, Error> {
+        self.check_initial(initial);
+        self.take(count, step)
+    }
+
+    fn observe_max(&self, value: T) -> bool {
+        let mut state = self.state();
+        if value <= state.current {
+            return false;
+        }
+        if value > state.ceiling {
+            let delta = value.wrapping_sub(state.current).to_bits();
+            if self.reserve(&mut state, delta, T::from_bits(1)).is_err() {
+                return false;
+            }
+        }
+        state.current = state.current.max(value);
+        true
+    }
+
+    fn current(&self) -> T {
+        self.state().current
+    }
+}
+
+impl<T: Primitive> fmt::Debug for SqliteSource<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.state();
+        f.debug_struct("SqliteSource")
+            .field("name", &self.name)
+            .field("block", &self.block)
+            .field("current_value", &state.current)
+            .field("ceiling", &state.ceiling)
+            .field("connected", &state.connection.is_some())
+            .finish()
+    }
+}
@@ -0,0 +1,30 @@
+//! The `@ThreadCached(N)` built-in attribute : each thread creates its ids from a block
+//! of `N` values reserved from the "current value".
+
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __thread_cached_numid {
+    ($name:ident($ty:ty) $size:expr) => {
+        impl $name {
+            #[doc(hidden)]
+            #[inline]
+            fn __try_next() -> Result<$ty, $crate::Error> {
+                $crate::_std::thread_local! {
+                    static CACHE: $crate::_core::cell::Cell<Option<($ty, usize)>> =
+                        $crate::_core::cell::Cell::new(None);
+                }
+
+                CACHE.with(|cache| {
+                    $crate::__cached_next(
+                        cache,
+                        $name::__source(),
+                        $size,
+                        $name::OVERFLOW,
+                        $name::INITIAL_VALUE,
+                        $name::STEP,
+                    )
+                })
+            }
+        }
+    };
+}
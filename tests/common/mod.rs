@@ -0,0 +1,10 @@
+use std::fs;
+use std::path::PathBuf;
+
+/// Create an empty directory named after `name` and the process in the temporary directory.
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("numid-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
@@ -4,6 +4,6 @@ use numid::numid;
 numid!(struct Test -> 100);
 
 fn main() {
-    const _CONST: Test = Test::const_create_lower(101); //~ ERROR any use of this value will cause an error
+    const _CONST: Test = Test::const_create_lower(101); //~ ERROR the value of an id created with `const_create_lower` must be less than or equal to `INITIAL_VALUE`
 }
  
@@ -0,0 +1,63 @@
+#![cfg(feature = "derive")]
+
+use numid::NumId;
+
+#[derive(NumId)]
+#[numid(init = 10, repr = u32, clone = "reproduce", step = 2)]
+struct Even(u32);
+
+#[derive(NumId)]
+#[numid(prefix = "plain_")]
+pub struct Plain(u64);
+
+#[derive(NumId)]
+#[numid(overflow = "saturate", init = 254, parse = "lower")]
+struct Small(u8);
+
+#[test]
+#[allow(clippy::clone_on_copy)]
+fn derive_options() {
+    assert_eq!(Even::INITIAL_VALUE, 10);
+    assert_eq!(Even::STEP, 2);
+    let id = Even::new();
+    assert_eq!(id.value(), 12);
+    assert_eq!(Even::new().value(), 14);
+    assert_eq!(id.clone().value(), 16);
+    assert_eq!(Even::reserve(2).map(Even::value).collect::<Vec<_>>(), vec![18, 20]);
+    assert!(Even::create_lower(3) < id);
+    assert_eq!(format!("{:?} {}", id, id), "Even(12) 12");
+
+    assert_eq!(Small::new().value(), 255);
+    assert_eq!(Small::try_new(), Err(numid::Error::Exhausted));
+    assert_eq!(Small::OVERFLOW, numid::Overflow::Saturate);
+    assert_eq!("0xfe".parse::<Small>().map(Small::value), Ok(254));
+    assert!("255".parse::<Small>().is_err());
+}
+
+#[test]
+#[allow(clippy::clone_on_copy)]
+fn derive_num_id_trait() {
+    fn next<I: numid::NumId>() -> I {
+        I::new()
+    }
+
+    let id: Plain = next();
+    assert_eq!(id.value(), 1);
+    assert_ne!(Plain::new().clone(), id);
+    assert_eq!(Plain::current_value(), 2);
+    assert_eq!(format!("{}", id), "plain_1");
+    assert_eq!("plain_1".parse::<Plain>(), Ok(id));
+}
+
+static SOURCE: numid::Counter<u64> =
+    numid::Counter::from_atomic(<<u64 as numid::Primitive>::Atomic>::new(40));
+
+#[derive(NumId)]
+#[numid(source = crate::SOURCE, init = 40)]
+struct Sourced(u64);
+
+#[test]
+fn derive_source() {
+    assert_eq!(Sourced::new().value(), 41);
+    assert_eq!(SOURCE.current_value(), 41);
+}
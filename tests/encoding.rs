@@ -0,0 +1,81 @@
+use numid::{numid, ParseError};
+
+macro_rules! round_trip {
+    ($($ty:ident),+) => {
+        $({
+            numid!(struct Id($ty) -> $ty::MAX);
+
+            for &value in &[$ty::MIN, $ty::MIN / 3, 0, 1, 61, 62, 63, $ty::MAX / 7, $ty::MAX] {
+                let id = Id::create_lower(value);
+                assert_eq!(Id::from_base62(&id.to_base62()), Ok(id));
+                assert_eq!(Id::from_crockford32(&id.to_crockford32()), Ok(id));
+                assert_eq!(Id::from_crockford32(&id.to_crockford32().to_ascii_lowercase()), Ok(id));
+            }
+        })+
+    };
+}
+
+#[test]
+fn encoding_round_trip() {
+    round_trip!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+}
+
+#[test]
+fn encoding_values() {
+    numid!(struct Id(u128) -> u128::MAX);
+
+    let zero = Id::create_lower(0);
+    assert_eq!(zero.to_base62(), "0");
+    assert_eq!(zero.to_crockford32(), "0");
+
+    let id = Id::create_lower(62 * 62 + 61);
+    assert_eq!(id.to_base62(), "10z");
+    assert_eq!(format!("{:>5}", id.to_base62()), "  10z");
+    assert_eq!(Id::create_lower(31).to_crockford32(), "Z");
+
+    let max = Id::create_lower(u128::MAX);
+    assert_eq!(max.to_base62(), "7n42DGM5Tflk9n8mt7Fhc7");
+    assert_eq!(max.to_crockford32().len(), 26);
+    assert_eq!(Id::from_crockford32(&max.to_crockford32()), Ok(max));
+}
+
+#[test]
+fn encoding_decode_errors() {
+    numid!(struct Id(u16) -> u16::MAX);
+
+    assert_eq!(
+        Id::from_crockford32("1O-il").map(Id::value),
+        Ok(32 * 32 * 32 + 32 + 1)
+    );
+    assert_eq!(Id::from_crockford32("10L1"), Id::from_crockford32("1011"));
+    assert_eq!(Id::from_crockford32(""), Err(ParseError::Empty));
+    assert_eq!(Id::from_crockford32("-"), Err(ParseError::Empty));
+    assert_eq!(Id::from_crockford32("U"), Err(ParseError::InvalidDigit));
+    assert_eq!(Id::from_crockford32("2000"), Err(ParseError::OutOfRange));
+    assert_eq!(Id::from_base62("a-b"), Err(ParseError::InvalidDigit));
+    assert_eq!(Id::from_base62("H32"), Err(ParseError::OutOfRange));
+    assert_eq!(
+        Id::from_base62("zzzzzzzzzzzzzzzzzzzzzzzz"),
+        Err(ParseError::OutOfRange)
+    );
+
+    numid!(@ParseLower struct Lower(u16) -> 10);
+    assert_eq!(
+        Lower::from_base62("B"),
+        Err(ParseError::Rejected(numid::Error::AboveInitialValue))
+    );
+}
+
+#[test]
+fn encoding_decode_create() {
+    numid!(@ParseCreate struct Create(u32) -> 10);
+
+    let id = Create::new();
+    let encoded = id.to_base62();
+    assert_eq!(Create::from_base62(&encoded), Ok(id));
+    assert_eq!(Create::from_base62(&encoded), Ok(id));
+    let encoded = id.to_crockford32();
+    assert_eq!(Create::from_crockford32(&encoded), Ok(id));
+    assert_eq!(Create::from_base62("z").map(Create::value), Ok(61));
+    assert_eq!(Create::current_value(), 11);
+}
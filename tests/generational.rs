@@ -0,0 +1,74 @@
+#![cfg(feature = "alloc")]
+
+use numid::{numid, GenerationalAllocator, GenerationalId};
+
+numid!(@Generational struct EntityId(u32));
+numid!(@Generational struct SmallId(u8));
+
+#[test]
+fn generational_reuse() {
+    let mut allocator = GenerationalAllocator::new();
+    let ids: Vec<EntityId> = (0..4).map(|_| allocator.allocate()).collect();
+    assert_eq!(ids.iter().map(|id| id.index()).collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+    assert!(ids.iter().all(|id| id.generation() == 0));
+    assert_eq!(allocator.len(), 4);
+
+    assert!(allocator.free(ids[2]));
+    assert!(!allocator.free(ids[2]));
+    assert!(!allocator.is_alive(ids[2]));
+    assert_eq!(allocator.len(), 3);
+
+    let reused = allocator.allocate();
+    assert_eq!(reused, EntityId::from_parts(2, 1));
+    assert!(allocator.is_alive(reused));
+    assert!(!allocator.is_alive(ids[2]));
+    assert!(!allocator.free(ids[2]));
+
+    assert_eq!(allocator.allocate().index(), 4);
+    assert!(!allocator.is_alive(EntityId::from_parts(10, 0)));
+}
+
+#[test]
+fn generational_retire() {
+    let mut allocator: GenerationalAllocator<SmallId> = GenerationalAllocator::new();
+
+    let mut id = allocator.allocate();
+    for generation in 1..=255 {
+        assert!(allocator.free(id));
+        id = allocator.allocate();
+        assert_eq!((id.index(), id.generation()), (0, generation));
+    }
+
+    // the generation can't be bumped anymore, the index 0 is retired
+    assert!(allocator.free(id));
+    assert_eq!(allocator.allocate().index(), 1);
+    assert!(!allocator.is_empty());
+}
+
+#[test]
+fn generational_exhausted() {
+    let mut allocator: GenerationalAllocator<SmallId> = GenerationalAllocator::new();
+    for _ in 0..256 {
+        allocator.try_allocate().unwrap();
+    }
+    assert_eq!(allocator.try_allocate(), Err(numid::Error::Exhausted));
+}
+
+#[test]
+fn generational_fmt() {
+    let id = EntityId::from_parts(26, 3);
+    assert_eq!(format!("{:?}", id), "EntityId { index: 26, generation: 3 }");
+    assert_eq!(format!("{:x}", id), "1av3");
+    assert_eq!(format!("{:b}", id), "11010v11");
+    #[cfg(feature = "display")]
+    assert_eq!(format!("{}", id), "26v3");
+}
+
+#[test]
+fn generational_trait() {
+    fn parts<I: GenerationalId>(id: I) -> (I::Raw, I::Raw) {
+        (id.index(), id.generation())
+    }
+
+    assert_eq!(parts(EntityId::from_parts(7, 2)), (7, 2));
+}
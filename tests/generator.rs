@@ -0,0 +1,61 @@
+#![cfg(feature = "alloc")]
+
+use numid::{numid, Generator};
+use std::collections::HashSet;
+use std::thread;
+
+numid!(struct Id(u32) -> 100);
+
+#[test]
+fn generator_independent() {
+    let first = Generator::<Id>::new();
+    let second = Generator::<Id>::new();
+
+    assert_eq!(first.next_id().value(), 101);
+    assert_eq!(first.next_id().value(), 102);
+    assert_eq!(second.next_id().value(), 101);
+    assert_eq!(first.current_value(), 102);
+    assert_eq!(second.current_value(), 101);
+
+    assert!(second.replace_current_value(200));
+    assert_eq!(second.create_maybe(150), None);
+    assert_eq!(second.create_maybe(250).unwrap().value(), 250);
+    assert_eq!(second.try_next_id().unwrap().value(), 251);
+    assert_eq!(second.reserve(3).map(Id::value).collect::<Vec<_>>(), vec![252, 253, 254]);
+
+    assert_eq!(Id::current_value(), Id::INITIAL_VALUE);
+}
+
+#[test]
+fn generator_clone_threads() {
+    let generator = Generator::<Id>::with_current_value(1000);
+
+    let handles: Vec<_> = (0..8)
+        .map(|_| {
+            let generator = generator.clone();
+            thread::spawn(move || (0..1000).map(|_| generator.next_id()).collect::<Vec<_>>())
+        })
+        .collect();
+
+    let mut ids = HashSet::new();
+    for handle in handles {
+        for id in handle.join().unwrap() {
+            assert!(ids.insert(id), "duplicate id : {:?}", id);
+        }
+    }
+    assert_eq!(generator.current_value(), 9000);
+    assert_eq!(Id::current_value(), Id::INITIAL_VALUE);
+}
+
+#[test]
+fn generator_overflow() {
+    numid!(@OverflowWrap struct Small(u8) -> 254);
+
+    let generator = Generator::<Small>::new();
+    assert_eq!(generator.next_id().value(), 255);
+    assert_eq!(generator.next_id().value(), 255);
+    assert_eq!(generator.epoch(), 1);
+
+    let exhausted = Generator::<Id>::with_current_value(u32::MAX);
+    assert_eq!(exhausted.try_next_id(), Err(numid::Error::Exhausted));
+}
@@ -0,0 +1,124 @@
+#![cfg(feature = "std")]
+
+use std::collections::HashSet;
+use std::fs;
+use std::sync::Arc;
+use std::thread;
+
+use numid::{numid, Error, HiLoGenerator};
+
+mod common;
+use common::temp_dir;
+
+#[test]
+fn hilo_blocks() {
+    numid!(struct Id -> 100);
+
+    let dir = temp_dir("hilo-blocks");
+    let path = dir.join("id");
+    let generator = HiLoGenerator::<Id>::open(&path, 10).unwrap();
+    assert!(!path.exists());
+    assert_eq!(generator.next_id().unwrap().value(), 101);
+    assert_eq!(fs::read_to_string(&path).unwrap(), "110\n");
+    for value in 102..=110 {
+        assert_eq!(generator.next_id().unwrap().value(), value);
+    }
+    assert_eq!(generator.ceiling(), 110);
+    assert_eq!(fs::read_to_string(&path).unwrap(), "110\n");
+    assert_eq!(generator.next_id().unwrap().value(), 111);
+    assert_eq!(fs::read_to_string(&path).unwrap(), "120\n");
+    assert_eq!(Id::current_value(), 100);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn hilo_restart() {
+    numid!(@Step(5) struct Id(u32));
+
+    let dir = temp_dir("hilo-restart");
+    let path = dir.join("id");
+    let mut created = HashSet::new();
+    for restart in 0..5 {
+        // The generator is dropped without any shutdown, like after a crash.
+        let generator = HiLoGenerator::<Id>::open(&path, 7).unwrap();
+        for _ in 0..restart * 4 {
+            let id = generator.next_id().unwrap();
+            assert!(created.iter().all(|other: &Id| *other < id));
+            assert!(created.insert(id));
+        }
+    }
+    assert_eq!(created.len(), 40);
+    assert!(created.iter().all(|id| id.value() % 5 == 0));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn hilo_threads() {
+    numid!(struct Id(u64));
+
+    let dir = temp_dir("hilo-threads");
+    let generator = Arc::new(HiLoGenerator::<Id>::open(dir.join("id"), 16).unwrap());
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let generator = Arc::clone(&generator);
+            thread::spawn(move || {
+                (0..100)
+                    .map(|_| generator.next_id().unwrap())
+                    .collect::<Vec<_>>()
+            })
+        })
+        .collect();
+    let mut created = HashSet::new();
+    for handle in handles {
+        for id in handle.join().unwrap() {
+            assert!(created.insert(id));
+        }
+    }
+    assert_eq!(created.len(), 400);
+    assert_eq!(generator.current_value(), 400);
+    assert_eq!(generator.ceiling(), 400);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn hilo_exhausted() {
+    numid!(struct Id(u8) -> 250);
+
+    let dir = temp_dir("hilo-exhausted");
+    let path = dir.join("id");
+    let generator = HiLoGenerator::<Id>::open(&path, 100).unwrap();
+    for value in 251..=255 {
+        assert_eq!(generator.next_id().unwrap().value(), value);
+    }
+    assert_eq!(fs::read_to_string(&path).unwrap(), "255\n");
+    let error = generator.next_id().unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::Other);
+    assert_eq!(
+        error.into_inner().unwrap().downcast_ref(),
+        Some(&Error::Exhausted)
+    );
+    drop(generator);
+
+    fs::write(&path, "-1\n").unwrap();
+    let error = HiLoGenerator::<Id>::open(&path, 100).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn hilo_locked() {
+    numid!(struct Id);
+
+    let dir = temp_dir("hilo-locked");
+    let path = dir.join("id");
+    let generator = HiLoGenerator::<Id>::open(&path, 10).unwrap();
+    let error = HiLoGenerator::<Id>::open(&path, 10).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::WouldBlock);
+    assert_eq!(generator.next_id().unwrap().value(), 1);
+
+    drop(generator);
+    let generator = HiLoGenerator::<Id>::open(&path, 10).unwrap();
+    assert_eq!(generator.next_id().unwrap().value(), 11);
+    fs::remove_dir_all(&dir).unwrap();
+}
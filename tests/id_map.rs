@@ -0,0 +1,81 @@
+#![cfg(feature = "alloc")]
+
+use numid::{numid, IdMap, IdMapEntry};
+
+#[test]
+fn id_map_basic() {
+    numid!(struct Id(u32) -> 100);
+
+    let ids: Vec<Id> = (0..10).map(|_| Id::new()).collect();
+    let mut map = IdMap::new();
+    assert!(map.is_empty());
+    for (i, &id) in ids.iter().enumerate().rev() {
+        assert_eq!(map.insert(id, i), None);
+    }
+    assert_eq!(map.len(), 10);
+    assert_eq!(map.insert(ids[3], 30), Some(3));
+    assert_eq!(map.get(ids[3]), Some(&30));
+    assert_eq!(map[ids[4]], 4);
+    map[ids[4]] += 40;
+    assert_eq!(map.get(ids[4]), Some(&44));
+
+    assert_eq!(map.remove(ids[0]), Some(0));
+    assert_eq!(map.remove(ids[0]), None);
+    assert_eq!(map.len(), 9);
+    assert!(!map.contains_key(ids[0]));
+    assert!(!map.contains_key(Id::new()));
+    assert_eq!(map.get(Id::create_lower(3)), None);
+    assert_eq!(map.remove(Id::create_lower(3)), None);
+
+    let keys: Vec<Id> = map.keys().collect();
+    assert_eq!(keys, ids[1..].to_vec());
+    assert_eq!(map.iter().len(), 9);
+    assert_eq!(map.iter().next_back(), Some((ids[9], &9)));
+    for (_, value) in &mut map {
+        *value += 1;
+    }
+    assert_eq!(
+        map.values().sum::<usize>(),
+        2 + 3 + 31 + 45 + 6 + 7 + 8 + 9 + 10
+    );
+
+    let copy: IdMap<Id, usize> = map.iter().map(|(id, &value)| (id, value)).collect();
+    assert_eq!(copy, map);
+    map.clear();
+    assert!(map.is_empty());
+    assert_eq!(map.iter().next(), None);
+}
+
+#[test]
+fn id_map_entry() {
+    numid!(@Step(2) struct Id(u8));
+
+    let (a, b) = (Id::new(), Id::new());
+    let mut map: IdMap<Id, Vec<u8>> = IdMap::new();
+    map.entry(a).or_default().push(1);
+    map.entry(a).or_default().push(2);
+    map.entry(b)
+        .and_modify(|v| v.push(9))
+        .or_insert_with(|| vec![0]);
+    assert_eq!(map[a], vec![1, 2]);
+    assert_eq!(map[b], vec![0]);
+
+    match map.entry(a) {
+        IdMapEntry::Occupied(mut entry) => {
+            assert_eq!(entry.key(), a);
+            assert_eq!(entry.insert(vec![3]), vec![1, 2]);
+            assert_eq!(entry.remove(), vec![3]);
+        }
+        IdMapEntry::Vacant(_) => unreachable!(),
+    }
+    assert_eq!(map.len(), 1);
+    assert_eq!(format!("{:?}", map), "{Id(4): [0]}");
+}
+
+#[test]
+#[should_panic(expected = "can't be used as a key of an `IdMap`")]
+fn id_map_lower_key() {
+    numid!(struct Id -> 10);
+
+    IdMap::new().insert(Id::create_lower(5), ());
+}
@@ -0,0 +1,170 @@
+#![cfg(feature = "alloc")]
+
+use numid::{numid, IdSet, IdSetDecodeError};
+
+numid!(struct Id(u32) -> u32::MAX);
+numid!(struct SignedId(i16) -> i16::MAX);
+
+fn set(values: impl IntoIterator<Item = u32>) -> IdSet<Id> {
+    values.into_iter().map(Id::create_lower).collect()
+}
+
+fn values(set: &IdSet<Id>) -> Vec<u32> {
+    set.iter().map(|id| id.value()).collect()
+}
+
+#[test]
+fn id_set_basic() {
+    let mut set = IdSet::new();
+    assert!(set.is_empty());
+    assert!(set.insert(Id::create_lower(70_000)));
+    assert!(set.insert(Id::create_lower(5)));
+    assert!(set.insert(Id::create_lower(u32::MAX)));
+    assert!(!set.insert(Id::create_lower(5)));
+    assert_eq!(set.len(), 3);
+    assert!(set.contains(Id::create_lower(70_000)));
+    assert!(!set.contains(Id::create_lower(6)));
+    assert_eq!(values(&set), vec![5, 70_000, u32::MAX]);
+    assert_eq!(set.iter().len(), 3);
+    assert_eq!(set.first(), Some(Id::create_lower(5)));
+    assert_eq!(set.last(), Some(Id::create_lower(u32::MAX)));
+
+    assert!(set.remove(Id::create_lower(70_000)));
+    assert!(!set.remove(Id::create_lower(70_000)));
+    assert_eq!(values(&set), vec![5, u32::MAX]);
+    set.clear();
+    assert!(set.is_empty());
+    assert_eq!(set.first(), None);
+    assert_eq!(format!("{:?}", self::set(vec![2, 1])), "{Id(1), Id(2)}");
+}
+
+#[test]
+fn id_set_dense() {
+    let mut set = set((0..100_000).filter(|v| v % 3 != 0));
+    assert_eq!(set.len(), 66_666);
+    assert_eq!(
+        values(&set),
+        (0..100_000).filter(|v| v % 3 != 0).collect::<Vec<_>>()
+    );
+    assert_eq!(set.rank(Id::create_lower(10)), 7);
+    assert_eq!(set.select(6), Some(Id::create_lower(10)));
+    assert_eq!(set.select(66_666), None);
+
+    for value in (0..100_000).filter(|v| v % 3 != 0 && *v > 10) {
+        assert!(set.remove(Id::create_lower(value)));
+    }
+    assert_eq!(values(&set), vec![1, 2, 4, 5, 7, 8, 10]);
+}
+
+#[test]
+fn id_set_operations() {
+    let sparse = set((0..200_000).step_by(100));
+    let dense = set((0..150_000).filter(|v| v % 2 == 0));
+
+    let union = sparse.union(&dense);
+    let expected: Vec<u32> = (0..200_000)
+        .filter(|v| v % 100 == 0 || (v % 2 == 0 && *v < 150_000))
+        .collect();
+    assert_eq!(values(&union), expected);
+    assert_eq!(&sparse | &dense, union);
+
+    let intersection = sparse.intersection(&dense);
+    assert_eq!(
+        values(&intersection),
+        (0..150_000).step_by(100).collect::<Vec<_>>()
+    );
+    assert_eq!(&sparse & &dense, intersection);
+
+    let difference = sparse.difference(&dense);
+    assert_eq!(
+        values(&difference),
+        (150_000..200_000).step_by(100).collect::<Vec<_>>()
+    );
+    assert_eq!(&sparse - &dense, difference);
+
+    let difference = dense.difference(&sparse);
+    let expected: Vec<u32> = (0..150_000)
+        .filter(|v| v % 2 == 0 && v % 100 != 0)
+        .collect();
+    assert_eq!(values(&difference), expected);
+    assert!(dense.difference(&dense).is_empty());
+}
+
+#[test]
+fn id_set_rank_select() {
+    let set = set(vec![3, 10, 65_536, 65_537, 1 << 20]);
+    assert_eq!(set.rank(Id::create_lower(0)), 0);
+    assert_eq!(set.rank(Id::create_lower(3)), 1);
+    assert_eq!(set.rank(Id::create_lower(65_535)), 2);
+    assert_eq!(set.rank(Id::create_lower(65_537)), 4);
+    assert_eq!(set.rank(Id::create_lower(u32::MAX)), 5);
+    for (n, id) in set.iter().enumerate() {
+        assert_eq!(set.select(n), Some(id));
+        assert_eq!(set.rank(id), n + 1);
+    }
+}
+
+#[test]
+fn id_set_signed_order() {
+    let set: IdSet<SignedId> = vec![5, -1, i16::MIN, 0, i16::MAX]
+        .into_iter()
+        .map(SignedId::create_lower)
+        .collect();
+    let values: Vec<i16> = set.iter().map(|id| id.value()).collect();
+    assert_eq!(values, vec![i16::MIN, -1, 0, 5, i16::MAX]);
+    assert_eq!(set.rank(SignedId::create_lower(-1)), 2);
+    assert_eq!(IdSet::from_bytes(&set.to_bytes()), Ok(set));
+}
+
+#[test]
+fn id_set_bytes() {
+    let empty = IdSet::<Id>::new();
+    assert_eq!(IdSet::from_bytes(&empty.to_bytes()), Ok(empty));
+
+    let set = set((0..10_000).chain((1 << 20..(1 << 20) + 100).step_by(7)));
+    let bytes = set.to_bytes();
+    assert!(bytes.len() < 10_000 / 8 + 8192);
+    assert_eq!(IdSet::from_bytes(&bytes), Ok(set.clone()));
+
+    assert_eq!(
+        IdSet::<Id>::from_bytes(&bytes[..bytes.len() - 1]),
+        Err(IdSetDecodeError::UnexpectedEnd)
+    );
+    let mut trailing = bytes.clone();
+    trailing.push(0);
+    assert_eq!(
+        IdSet::<Id>::from_bytes(&trailing),
+        Err(IdSetDecodeError::Invalid)
+    );
+    assert_eq!(
+        IdSet::<SignedId>::from_bytes(&bytes),
+        Err(IdSetDecodeError::WrongIdSize)
+    );
+    assert_eq!(
+        IdSet::<Id>::from_bytes(&[]),
+        Err(IdSetDecodeError::UnexpectedEnd)
+    );
+}
+
+#[test]
+fn id_set_bytes_type() {
+    numid!(struct SignedWide(i32) -> i32::MAX);
+    numid!(struct Byte(u8) -> u8::MAX);
+
+    let bytes = set(vec![1, 2, 3]).to_bytes();
+    assert_eq!(
+        IdSet::<SignedWide>::from_bytes(&bytes),
+        Err(IdSetDecodeError::WrongIdSize)
+    );
+
+    let set: IdSet<Byte> = vec![44, 255].into_iter().map(Byte::create_lower).collect();
+    let mut bytes = set.to_bytes();
+    assert_eq!(IdSet::from_bytes(&bytes), Ok(set));
+    // Replace 255 by 300 in the array.
+    let last = bytes.len() - 2;
+    bytes[last..].copy_from_slice(&300u16.to_le_bytes());
+    assert_eq!(
+        IdSet::<Byte>::from_bytes(&bytes),
+        Err(IdSetDecodeError::Invalid)
+    );
+}
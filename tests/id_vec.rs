@@ -0,0 +1,100 @@
+#![cfg(feature = "alloc")]
+
+use numid::{numid, IdVec};
+
+#[test]
+fn id_vec_basic() {
+    numid!(struct Id(u32) -> 100);
+
+    let mut vec = IdVec::new();
+    assert!(vec.is_empty());
+    assert_eq!(vec.last_key(), None);
+    let a: Id = vec.push("a");
+    let b = vec.push("b");
+    assert_eq!(a.value(), 0);
+    assert_eq!(b.value(), 1);
+    assert_eq!(Id::current_value(), 100);
+    assert_eq!(vec.len(), 2);
+    assert_eq!(vec.next_key().value(), 2);
+    assert_eq!(vec.last_key(), Some(b));
+
+    assert_eq!(vec[a], "a");
+    vec[b] = "B";
+    assert_eq!(vec.get(b), Some(&"B"));
+    assert_eq!(vec.get(Id::create_lower(99)), None);
+    assert!(!vec.contains_key(vec.next_key()));
+    assert_eq!(format!("{:?}", vec), "{Id(0): \"a\", Id(1): \"B\"}");
+
+    assert_eq!(vec.pop(), Some((b, "B")));
+    assert_eq!(vec.as_slice(), &["a"]);
+    assert_eq!(vec.push("c"), b);
+}
+
+#[test]
+fn id_vec_counter() {
+    numid!(struct Id(u8) -> 3);
+
+    let mut vec: IdVec<Id, u8> = (0..4).collect();
+    let id = Id::new();
+    assert_eq!(id.value(), 4);
+    assert!(!vec.contains_key(id));
+    assert_eq!(vec.get(id), None);
+    assert!(vec.indices().all(|key| key.value() <= Id::INITIAL_VALUE));
+    assert_eq!(vec[Id::create_lower(3)], 3);
+    vec.pop();
+    assert_eq!(vec.push(9).value(), 3);
+}
+
+#[test]
+fn id_vec_iter() {
+    numid!(@Step(10) struct Id(i8) -> 0);
+
+    let mut vec: IdVec<Id, usize> = (0..5).collect();
+    let keys: Vec<i8> = vec.indices().map(Id::value).collect();
+    assert_eq!(keys, vec![-128, -127, -126, -125, -124]);
+    assert_eq!(vec.iter_enumerated().len(), 5);
+    for (key, value) in vec.iter_enumerated_mut() {
+        *value += (key.value() as i16 + 128) as usize;
+    }
+    assert_eq!(vec.iter().copied().collect::<Vec<_>>(), vec![0, 2, 4, 6, 8]);
+    assert_eq!(
+        vec.iter_enumerated().next_back(),
+        Some((vec.last_key().unwrap(), &8))
+    );
+    assert!(vec.indices().all(|key| vec.contains_key(key)));
+    assert_eq!(vec.get(Id::create_lower(-128)), Some(&0));
+    assert_eq!(vec.get(Id::create_lower(0)), None);
+}
+
+#[test]
+#[should_panic]
+fn id_vec_full() {
+    numid!(struct Id(u8) -> 1);
+
+    let mut vec = IdVec::<Id, ()>::new();
+    assert_eq!(vec.push(()).value(), 0);
+    assert_eq!(vec.push(()).value(), 1);
+    vec.push(());
+}
+
+#[test]
+#[should_panic]
+fn id_vec_default_init() {
+    numid!(struct Id(u32));
+
+    let mut vec = IdVec::<Id, ()>::new();
+    assert_eq!(vec.push(()).value(), 0);
+    vec.push(());
+}
+
+#[test]
+fn id_vec_from_vec() {
+    numid!(struct Id(u8) -> 2);
+
+    let vec = IdVec::<Id, u8>::from(vec![0, 1, 2]);
+    assert_eq!(vec.last_key().map(Id::value), Some(2));
+    let result = std::panic::catch_unwind(|| IdVec::<Id, u8>::from_vec(vec![0, 1, 2, 3]));
+    assert!(result.is_err());
+    let result = std::panic::catch_unwind(|| IdVec::<Id, u16>::from((0..300).collect::<Vec<_>>()));
+    assert!(result.is_err());
+}
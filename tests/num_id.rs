@@ -0,0 +1,39 @@
+use numid::{numid, NumId};
+
+numid!(struct UserId(u32) -> 10);
+numid!(@CloneIsReproduce struct OrderId(u128));
+
+fn fresh_pair<I: NumId>() -> (I, I) {
+    (I::new(), I::try_new().unwrap())
+}
+
+fn lower_or_maybe<I: NumId>(value: I::Raw) -> Option<I> {
+    I::try_create_lower(value).ok().or_else(|| I::create_maybe(value))
+}
+
+#[test]
+fn num_id_generic() {
+    let (a, b) = fresh_pair::<UserId>();
+    assert_eq!((a.value(), b.value()), (11, 12));
+    assert_eq!(<UserId as NumId>::current_value(), 12);
+    assert_eq!(<UserId as NumId>::INITIAL_VALUE, 10);
+    assert_eq!(<UserId as NumId>::MAX_VALUE, u32::MAX);
+    assert_eq!(<UserId as NumId>::OVERFLOW, numid::Overflow::Panic);
+
+    assert_eq!(lower_or_maybe::<UserId>(5), Some(UserId::create_lower(5)));
+    assert_eq!(lower_or_maybe::<UserId>(12), None);
+    assert_eq!(lower_or_maybe::<UserId>(20).map(NumId::value), Some(20));
+    assert!(!<UserId as NumId>::replace_current_value(15));
+}
+
+#[test]
+fn num_id_reproduce() {
+    fn reproduce_all<I: NumId>(ids: &[I]) -> Vec<I> {
+        ids.iter().map(|id| id.reproduce()).collect()
+    }
+
+    let ids = [OrderId::create_lower(0), OrderId::new()];
+    let reproduced = reproduce_all(&ids);
+    assert_eq!(reproduced[0], ids[0]);
+    assert!(reproduced[1] > ids[1]);
+}
@@ -1,3 +1,6 @@
+// The tests predate clippy, they are kept as written.
+#![allow(clippy::bool_assert_comparison, clippy::precedence)]
+
 use numid::numid;
 // rustc v1.26+
 numid!(struct Id128(u128) -> 1u128 << 100);
@@ -0,0 +1,70 @@
+use numid::numid;
+use std::collections::HashSet;
+
+const KEY: u64 = 0x0123_4567_89ab_cdef;
+
+#[test]
+fn obfuscate_bijection() {
+    numid!(struct Byte(u8) -> u8::MAX);
+    numid!(struct Short(i16) -> i16::MAX);
+
+    let bytes: HashSet<u8> = (0..=u8::MAX)
+        .map(|v| Byte::create_lower(v).obfuscate(KEY))
+        .collect();
+    assert_eq!(bytes.len(), 256);
+    for value in 0..=u8::MAX {
+        let id = Byte::create_lower(value);
+        assert_eq!(Byte::deobfuscate(id.obfuscate(KEY), KEY), Ok(id));
+    }
+
+    let mut shorts = HashSet::new();
+    for value in i16::MIN..=i16::MAX {
+        let id = Short::create_lower(value);
+        assert!(shorts.insert(id.obfuscate(KEY)));
+        assert_eq!(Short::deobfuscate(id.obfuscate(KEY), KEY), Ok(id));
+    }
+}
+
+macro_rules! round_trip {
+    ($($ty:ident),+) => {
+        $({
+            numid!(struct Id($ty) -> $ty::MAX);
+
+            for &value in &[$ty::MIN, 0, 1, 2, $ty::MAX / 3, $ty::MAX] {
+                let id = Id::create_lower(value);
+                for &key in &[0, 1, KEY, u64::MAX] {
+                    assert_eq!(Id::deobfuscate(id.obfuscate(key), key), Ok(id));
+                }
+            }
+        })+
+    };
+}
+
+#[test]
+fn obfuscate_round_trip() {
+    round_trip!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+}
+
+#[test]
+fn obfuscate_deterministic() {
+    numid!(struct Id(u32));
+
+    // The permutation must not change between versions and platforms.
+    let values: Vec<u32> = (0..4).map(|_| Id::new().obfuscate(KEY)).collect();
+    assert_eq!(values, vec![3728341151, 1227799545, 3733969844, 1597068187]);
+    assert_ne!(
+        Id::create_lower(0).obfuscate(KEY),
+        Id::create_lower(0).obfuscate(KEY + 1)
+    );
+}
+
+#[test]
+fn obfuscate_parse_create() {
+    numid!(@ParseCreate struct Id(u32));
+
+    let id = Id::new();
+    let public = id.obfuscate(KEY);
+    assert_eq!(Id::deobfuscate(public, KEY), Ok(id));
+    assert_eq!(Id::deobfuscate(public, KEY), Ok(id));
+    assert_eq!(Id::current_value(), 1);
+}
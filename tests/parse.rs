@@ -0,0 +1,72 @@
+use numid::{numid, Error, ParseError};
+use std::convert::TryFrom;
+
+#[test]
+fn parse_round_trip() {
+    // All the values are accepted by `create_lower`.
+    numid!(struct Byte(u8) -> 255);
+    numid!(struct Signed(i16) -> i16::MAX);
+
+    for value in 0..=u8::MAX {
+        let id = Byte::create_lower(value);
+        for text in &[
+            format!("{}", id.value()),
+            format!("{:#x}", id),
+            format!("{:#X}", id),
+            format!("{:#o}", id),
+            format!("{:#b}", id),
+        ] {
+            assert_eq!(text.parse::<Byte>(), Ok(id), "{}", text);
+        }
+    }
+    for &value in &[i16::MIN, -300, -1, 0, 1, i16::MAX] {
+        let id = Signed::create_lower(value);
+        for text in &[
+            format!("{}", id.value()),
+            format!("{:#x}", id),
+            format!("{:#o}", id),
+            format!("{:#b}", id),
+        ] {
+            assert_eq!(text.parse::<Signed>(), Ok(id), "{}", text);
+        }
+    }
+}
+
+#[test]
+fn parse_errors() {
+    numid!(struct Id(u8));
+
+    assert_eq!(Id::try_from("0x2a").map(Id::value), Ok(42));
+    assert_eq!("".parse::<Id>(), Err(ParseError::Empty));
+    assert_eq!("0x".parse::<Id>(), Err(ParseError::Empty));
+    assert_eq!("12a".parse::<Id>(), Err(ParseError::InvalidDigit));
+    assert_eq!("0x-1".parse::<Id>(), Err(ParseError::InvalidDigit));
+    assert_eq!("0b102".parse::<Id>(), Err(ParseError::InvalidDigit));
+    assert_eq!("256".parse::<Id>(), Err(ParseError::OutOfRange));
+    assert_eq!("0x100".parse::<Id>(), Err(ParseError::OutOfRange));
+    assert_eq!("-1".parse::<Id>(), Err(ParseError::InvalidDigit));
+
+    // `@ParseAny` doesn't modify the "current value".
+    assert_eq!("200".parse::<Id>().map(Id::value), Ok(200));
+    assert_eq!(Id::current_value(), 0);
+}
+
+#[test]
+fn parse_validation() {
+    numid!(@ParseLower struct Lower(u32) -> 10);
+    numid!(@ParseCreate struct Create(u32) -> 10);
+
+    assert_eq!("0xa".parse::<Lower>(), Ok(Lower::create_lower(10)));
+    assert_eq!(
+        "11".parse::<Lower>(),
+        Err(ParseError::Rejected(Error::AboveInitialValue))
+    );
+
+    assert_eq!("20".parse::<Create>().map(Create::value), Ok(20));
+    assert_eq!(Create::current_value(), 20);
+    assert_eq!(
+        "20".parse::<Create>(),
+        Err(ParseError::Rejected(Error::NotGreaterThanCurrent))
+    );
+    assert_eq!(Create::new().value(), 21);
+}
@@ -0,0 +1,114 @@
+use numid::{numid, CounterSnapshot, NumId};
+
+#[cfg(feature = "std")]
+mod common;
+
+#[test]
+fn snapshot_restore() {
+    numid!(struct Id -> 10);
+
+    Id::new();
+    let snapshot = Id::snapshot();
+    assert_eq!(
+        snapshot,
+        CounterSnapshot {
+            current_value: 11,
+            epoch: 0
+        }
+    );
+    assert!(!Id::restore(snapshot));
+    assert!(Id::restore(CounterSnapshot {
+        current_value: 100,
+        epoch: 0
+    }));
+    assert_eq!(Id::new().value(), 101);
+    assert!(!Id::restore(snapshot));
+    assert_eq!(<Id as NumId>::snapshot().current_value, 101);
+}
+
+#[test]
+fn snapshot_restore_epoch() {
+    numid!(@OverflowWrap struct Id(u8) -> 200);
+
+    assert!(Id::restore(CounterSnapshot {
+        current_value: 210,
+        epoch: 2
+    }));
+    assert_eq!(Id::epoch(), 2);
+    assert_eq!(Id::current_value(), 210);
+    assert!(!Id::restore(CounterSnapshot {
+        current_value: 250,
+        epoch: 1
+    }));
+    assert_eq!(Id::new().value(), 211);
+}
+
+#[cfg(feature = "std")]
+mod file {
+    use numid::{load_high_water_mark, numid, save_high_water_mark};
+    use std::fs;
+
+    use crate::common::temp_dir;
+
+    #[test]
+    fn high_water_mark() {
+        numid!(struct Before(i64) -> -5);
+        numid!(struct After(i64) -> -5);
+
+        let dir = temp_dir("high-water-mark");
+        let path = dir.join("id");
+        assert!(!load_high_water_mark::<Before>(&path).unwrap());
+        Before::new();
+        save_high_water_mark::<Before>(&path).unwrap();
+        Before::new();
+        save_high_water_mark::<Before>(&path).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "-3\n");
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+
+        assert!(load_high_water_mark::<After>(&path).unwrap());
+        assert_eq!(After::new().value(), -2);
+        assert!(!load_high_water_mark::<After>(&path).unwrap());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn high_water_mark_invalid() {
+        numid!(struct Id(u8));
+
+        let dir = temp_dir("high-water-mark-invalid");
+        let path = dir.join("id");
+        fs::write(&path, "300\n").unwrap();
+        let error = load_high_water_mark::<Id>(&path).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(Id::current_value(), 0);
+        assert!(save_high_water_mark::<Id>(&dir.join("missing").join("id")).is_err());
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn high_water_mark_threads() {
+        numid!(struct Id);
+
+        let dir = temp_dir("high-water-mark-threads");
+        let path = dir.join("id");
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                let path = path.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..50 {
+                        Id::new();
+                        save_high_water_mark::<Id>(&path).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+        save_high_water_mark::<Id>(&path).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "200\n");
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,102 @@
+#![cfg(feature = "alloc")]
+
+use numid::numid;
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+#[test]
+fn pool_reuse_lowest() {
+    numid!(@Pool struct ChannelId(u16) -> 10);
+
+    let ids: Vec<_> = (0..5).map(|_| ChannelId::alloc().unwrap()).collect();
+    assert_eq!(ids[0].value(), 11);
+    assert_eq!(ids[4].value(), 15);
+
+    assert!(ChannelId::free(ids[3]));
+    assert!(ChannelId::free(ids[1]));
+    assert!(!ChannelId::free(ids[1]));
+    assert_eq!(ChannelId::free_count(), 2);
+
+    assert_eq!(ChannelId::alloc().unwrap().value(), 12);
+    assert_eq!(ChannelId::alloc().unwrap().value(), 14);
+    assert_eq!(ChannelId::alloc().unwrap().value(), 16);
+    assert_eq!(ChannelId::current_value(), 16);
+    assert_eq!(ChannelId::free_count(), 0);
+}
+
+#[test]
+fn pool_free_invalid() {
+    numid!(@Pool struct ChannelId(u16) -> 10);
+
+    assert!(!ChannelId::free(ChannelId::create_lower(10)));
+    let id = ChannelId::create_maybe(30).unwrap();
+    assert!(ChannelId::free(id));
+    assert!(!ChannelId::free(id));
+    assert_eq!(ChannelId::alloc().unwrap(), id);
+}
+
+#[test]
+fn pool_exhausted() {
+    numid!(@Pool @CloneIsNew struct ChannelId(u8) -> 253);
+
+    let a = ChannelId::alloc().unwrap();
+    let _b = ChannelId::alloc().unwrap();
+    assert_eq!(ChannelId::alloc(), Err(numid::Error::Exhausted));
+
+    assert!(ChannelId::free(a));
+    assert_eq!(ChannelId::alloc().unwrap().value(), 254);
+    assert_eq!(ChannelId::alloc(), Err(numid::Error::Exhausted));
+}
+
+#[test]
+fn pool_threads() {
+    numid!(@Pool struct ChannelId(u16));
+
+    let used = Arc::new(Mutex::new(HashSet::new()));
+    let handles: Vec<_> = (0..8)
+        .map(|_| {
+            let used = Arc::clone(&used);
+            thread::spawn(move || {
+                for _ in 0..500 {
+                    let id = ChannelId::alloc().unwrap();
+                    assert!(used.lock().unwrap().insert(id), "{:?} allocated twice", id);
+                    assert!(used.lock().unwrap().remove(&id));
+                    assert!(ChannelId::free(id));
+                }
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    assert!(ChannelId::current_value() <= 8);
+}
+
+#[test]
+fn pool_step() {
+    numid!(@Pool @Step(5) struct ChannelId(u16) -> 10);
+
+    let a = ChannelId::alloc().unwrap();
+    let _b = ChannelId::alloc().unwrap();
+    assert_eq!(ChannelId::current_value(), 20);
+    assert!(!ChannelId::free("18".parse().unwrap()));
+    assert!(ChannelId::free(a));
+    assert_eq!(ChannelId::free_count(), 1);
+    assert_eq!(ChannelId::alloc(), Ok(a));
+}
+
+#[test]
+fn pool_overflow() {
+    numid!(@Pool @OverflowWrap struct ChannelId(u8) -> 254);
+
+    let last = ChannelId::alloc().unwrap();
+    assert_eq!(last.value(), 255);
+    assert_eq!(ChannelId::alloc(), Err(numid::Error::Exhausted));
+    assert_eq!(ChannelId::epoch(), 0);
+    assert!(ChannelId::free(last));
+    assert_eq!(ChannelId::alloc(), Ok(last));
+    assert_eq!(ChannelId::alloc(), Err(numid::Error::Exhausted));
+}
@@ -0,0 +1,29 @@
+use numid::{numid, ParseError};
+
+numid!(@Prefix("usr_") struct UserId(u32) -> 41);
+numid!(@Prefix("ord_") struct OrderId(u32) -> 41);
+
+#[cfg(feature = "display")]
+#[test]
+fn prefix_display() {
+    let id = UserId::new();
+    assert_eq!(UserId::PREFIX, "usr_");
+    assert_eq!(format!("{}", id), "usr_42");
+    assert_eq!(format!("{:?}", id), "UserId(42)");
+    assert_eq!(format!("{:#x} {:o} {:b}", id, id, id), "0x2a 52 101010");
+    assert_eq!(id.to_string().parse::<UserId>(), Ok(id));
+}
+
+#[test]
+fn prefix_parse() {
+    assert_eq!("usr_7".parse::<UserId>().map(UserId::value), Ok(7));
+    assert_eq!("usr_0x2a".parse::<UserId>().map(UserId::value), Ok(42));
+    assert_eq!("ord_7".parse::<UserId>(), Err(ParseError::WrongPrefix));
+    assert_eq!("7".parse::<UserId>(), Err(ParseError::WrongPrefix));
+    assert_eq!("usr_".parse::<UserId>(), Err(ParseError::Empty));
+    assert_eq!("ord_7".parse::<OrderId>().map(OrderId::value), Ok(7));
+
+    numid!(struct Bare(u32));
+    assert_eq!(Bare::PREFIX, "");
+    assert_eq!("usr_7".parse::<Bare>(), Err(ParseError::InvalidDigit));
+}
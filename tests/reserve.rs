@@ -0,0 +1,119 @@
+use numid::numid;
+use std::collections::HashSet;
+use std::thread;
+
+#[test]
+fn reserve_block() {
+    numid!(struct Id(u32) -> 10);
+
+    let _ = Id::new();
+    let block = Id::reserve(5);
+    assert_eq!(block.len(), 5);
+    assert_eq!(Id::current_value(), 16);
+    assert_eq!(Id::new().value(), 17);
+
+    let values: Vec<u32> = block.clone().map(Id::value).collect();
+    assert_eq!(values, vec![12, 13, 14, 15, 16]);
+    let reversed: Vec<u32> = block.clone().rev().map(Id::value).collect();
+    assert_eq!(reversed, vec![16, 15, 14, 13, 12]);
+
+    assert!(block.clone().all(|id| block.contains(id)));
+    assert!(!block.contains(Id::new()));
+    assert!(!block.contains(Id::create_lower(10)));
+
+    let mut block = block;
+    assert_eq!(block.next().map(Id::value), Some(12));
+    assert_eq!(block.len(), 4);
+    assert!(!block.is_empty());
+
+    assert!(Id::reserve(0).is_empty());
+}
+
+#[test]
+fn reserve_contains() {
+    numid!(struct Id(i8) -> -100);
+
+    let block = Id::reserve(200);
+    let ids: Vec<Id> = block.clone().collect();
+    assert!(ids.iter().all(|&id| block.contains(id)));
+    assert!(!block.contains(Id::create_lower(-100)));
+    assert!(!block.contains(Id::new()));
+}
+
+#[test]
+fn reserve_split() {
+    numid!(struct Id(u64));
+
+    let (left, right) = Id::reserve(10).split_at(3);
+    assert_eq!(left.map(Id::value).collect::<Vec<_>>(), vec![1, 2, 3]);
+    assert_eq!(right.len(), 7);
+
+    let chunks: Vec<Vec<u64>> = Id::reserve(7)
+        .chunks(3)
+        .map(|chunk| chunk.map(Id::value).collect())
+        .collect();
+    assert_eq!(chunks, vec![vec![11, 12, 13], vec![14, 15, 16], vec![17]]);
+    assert_eq!(Id::reserve(7).chunks(3).len(), 3);
+}
+
+#[test]
+fn reserve_exhausted() {
+    numid!(struct Id(u8) -> 250);
+
+    assert_eq!(Id::try_reserve(6).unwrap_err(), numid::Error::Exhausted);
+    assert_eq!(Id::try_reserve(1000).unwrap_err(), numid::Error::Exhausted);
+    let block = Id::try_reserve(5).unwrap();
+    assert_eq!(block.clone().map(Id::value).max(), Some(255));
+    assert_eq!(block.rev().map(Id::value).next(), Some(255));
+    assert_eq!(Id::remaining(), 0);
+}
+
+#[test]
+fn reserve_wrap() {
+    numid!(@OverflowWrap struct Id(u8) -> 250);
+
+    let _ = Id::reserve(3);
+    let block = Id::reserve(4);
+    assert_eq!(block.map(Id::value).collect::<Vec<_>>(), vec![251, 252, 253, 254]);
+    assert_eq!(Id::epoch(), 1);
+}
+
+#[test]
+fn reserve_threads() {
+    numid!(struct Id(u128));
+
+    let handles: Vec<_> = (0..8)
+        .map(|_| {
+            thread::spawn(|| {
+                (0..100)
+                    .flat_map(|_| Id::reserve(10).chain(Some(Id::new())))
+                    .collect::<Vec<_>>()
+            })
+        })
+        .collect();
+
+    let mut ids = HashSet::new();
+    for handle in handles {
+        for id in handle.join().unwrap() {
+            assert!(ids.insert(id), "duplicate id : {:?}", id);
+        }
+    }
+    assert_eq!(ids.len(), 8 * 100 * 11);
+    assert_eq!(Id::current_value(), 8 * 100 * 11);
+}
+
+#[test]
+fn reserve_step() {
+    numid!(@Step(3) struct Id(u8) -> 240);
+
+    assert_eq!(Id::new().value(), 243);
+    let block = Id::reserve(4);
+    assert_eq!(block.clone().map(Id::value).collect::<Vec<_>>(), vec![246, 249, 252, 255]);
+    assert_eq!(block.clone().rev().map(Id::value).next(), Some(255));
+    assert!(block.clone().all(|id| block.contains(id)));
+    let (left, right) = block.split_at(1);
+    assert_eq!(right.map(Id::value).min(), Some(249));
+    assert!(!left.contains(Id::create_lower(240)));
+    assert_eq!(Id::remaining(), 0);
+    assert_eq!(Id::try_new().unwrap_err(), numid::Error::Exhausted);
+}
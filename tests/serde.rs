@@ -0,0 +1,70 @@
+#![cfg(feature = "serde")]
+
+use numid::numid;
+
+numid!(struct Id(u32) -> 10);
+numid!(@SerdeReplaceCurrent struct IdReplace(u128));
+
+#[test]
+fn serde_value() {
+    let id = Id::new();
+    let json = serde_json::to_string(&id).unwrap();
+    assert_eq!(json, "11");
+
+    let loaded: Id = serde_json::from_str("42").unwrap();
+    assert_eq!(loaded.value(), 42);
+    assert_eq!(Id::current_value(), 11);
+
+    let loaded: Vec<Id> = serde_json::from_str("[1, 5, 3]").unwrap();
+    assert_eq!(loaded, vec![Id::create_lower(1), Id::create_lower(5), Id::create_lower(3)]);
+}
+
+#[test]
+fn serde_invalid() {
+    assert!(serde_json::from_str::<Id>("-1").is_err());
+    assert!(serde_json::from_str::<Id>("\"1\"").is_err());
+}
+
+#[test]
+fn serde_replace_current() {
+    let loaded: Vec<IdReplace> = serde_json::from_str("[7, 300, 12]").unwrap();
+    assert_eq!(loaded[1].value(), 300);
+    assert_eq!(IdReplace::current_value(), 300);
+    assert_eq!(IdReplace::new().value(), 301);
+}
+
+numid!(@SerdeAsString struct IdString(u64) -> 1 << 60);
+numid!(@SerdeAsString @SerdeReplaceCurrent struct IdString128(u128) -> 1 << 100);
+numid!(@SerdeAsString struct IdStringI8(i8));
+
+#[test]
+fn serde_as_string() {
+    let id = IdString::new();
+    let json = serde_json::to_string(&id).unwrap();
+    assert_eq!(json, "\"1152921504606846977\"");
+    assert_eq!(serde_json::from_str::<IdString>(&json).unwrap(), id);
+    assert_eq!(serde_json::from_str::<IdString>("12").unwrap().value(), 12);
+    assert_eq!(IdString::current_value(), (1 << 60) + 1);
+
+    assert!(serde_json::from_str::<IdString>("\"abc\"").is_err());
+    assert!(serde_json::from_str::<IdString>("\"-1\"").is_err());
+    assert!(serde_json::from_str::<IdString>("-1").is_err());
+    assert!(serde_json::from_str::<IdString>("1.5").is_err());
+}
+
+#[test]
+fn serde_as_string_u128() {
+    let json = format!("\"{}\"", u128::MAX);
+    let loaded: IdString128 = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded.value(), u128::MAX);
+    assert_eq!(serde_json::to_string(&loaded).unwrap(), json);
+    assert_eq!(IdString128::current_value(), u128::MAX);
+}
+
+#[test]
+fn serde_as_string_signed() {
+    assert_eq!(serde_json::from_str::<IdStringI8>("\"-128\"").unwrap().value(), -128);
+    assert_eq!(serde_json::from_str::<IdStringI8>("-5").unwrap().value(), -5);
+    assert!(serde_json::from_str::<IdStringI8>("128").is_err());
+    assert_eq!(serde_json::to_string(&IdStringI8::create_lower(-3)).unwrap(), "\"-3\"");
+}
@@ -0,0 +1,39 @@
+#![cfg(all(feature = "std", unix))]
+
+use std::fs;
+
+use numid::{numid, Error, SharedGenerator};
+
+mod common;
+use common::temp_dir;
+
+#[test]
+fn shared_generators() {
+    numid!(@Step(3) struct Small(u8));
+
+    let dir = temp_dir("shared-generators");
+    let path = dir.join("id");
+    let a = SharedGenerator::<Small>::open(&path).unwrap();
+    let b = SharedGenerator::<Small>::open(&path).unwrap();
+    assert_eq!(a.current_value().unwrap(), 0);
+    assert_eq!(a.next_id().unwrap().value(), 3);
+    let block: Vec<u8> = b.reserve(3).unwrap().map(Small::value).collect();
+    assert_eq!(block, vec![6, 9, 12]);
+    assert_eq!(a.next_id().unwrap().value(), 15);
+    assert!(b.replace_current_value(249).unwrap());
+    assert!(!a.replace_current_value(10).unwrap());
+    assert_eq!(a.next_id().unwrap().value(), 252);
+    assert_eq!(b.next_id().unwrap().value(), 255);
+    let error = a.next_id().unwrap_err();
+    assert_eq!(
+        error.into_inner().unwrap().downcast_ref(),
+        Some(&Error::Exhausted)
+    );
+    assert!(b.reserve(1).is_err());
+    assert_eq!(b.current_value().unwrap(), 255);
+
+    fs::write(&path, "255").unwrap();
+    let error = a.current_value().unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    fs::remove_dir_all(&dir).unwrap();
+}
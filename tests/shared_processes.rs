@@ -0,0 +1,92 @@
+//! Several processes creating ids with the same `SharedGenerator`. The test has no harness : the
+//! binary is started again as the child processes, which only create ids.
+
+#[cfg(unix)]
+mod common;
+
+fn main() {
+    #[cfg(unix)]
+    processes::main();
+}
+
+#[cfg(unix)]
+mod processes {
+    use std::collections::HashSet;
+    use std::fs;
+    use std::process::Command;
+
+    use numid::{numid, SharedGenerator};
+
+    use crate::common::temp_dir;
+
+    numid!(struct Id(u64) -> 1000);
+
+    /// The path of the counter given to the child processes.
+    const CHILD_PATH: &str = "NUMID_SHARED_CHILD_PATH";
+    /// The path of the file where a child process writes its ids.
+    const CHILD_OUTPUT: &str = "NUMID_SHARED_CHILD_OUTPUT";
+    const CHILD_IDS: usize = 300;
+
+    pub fn main() {
+        match (std::env::var_os(CHILD_PATH), std::env::var_os(CHILD_OUTPUT)) {
+            (Some(path), Some(output)) => child(path, output),
+            _ => shared_processes(),
+        }
+    }
+
+    /// Create ids in a child process started by `shared_processes`.
+    fn child(path: std::ffi::OsString, output: std::ffi::OsString) {
+        let generator = SharedGenerator::<Id>::open(path).unwrap();
+        let mut ids = Vec::new();
+        while ids.len() < CHILD_IDS {
+            if ids.len() % 100 == 0 {
+                ids.extend(generator.reserve(10).unwrap());
+            } else {
+                ids.push(generator.next_id().unwrap());
+            }
+        }
+        let lines: Vec<String> = ids.iter().map(|id| id.value().to_string()).collect();
+        fs::write(output, lines.join("\n")).unwrap();
+    }
+
+    fn shared_processes() {
+        let dir = temp_dir("shared-processes");
+        let path = dir.join("id");
+        let children: Vec<_> = (0..4)
+            .map(|child| {
+                let output = dir.join(format!("child-{}", child));
+                let process = Command::new(std::env::current_exe().unwrap())
+                    .env(CHILD_PATH, &path)
+                    .env(CHILD_OUTPUT, &output)
+                    .spawn()
+                    .unwrap();
+                (process, output)
+            })
+            .collect();
+
+        let mut created = HashSet::new();
+        for (mut process, output) in children {
+            assert!(process.wait().unwrap().success());
+            let ids: Vec<u64> = fs::read_to_string(output)
+                .unwrap()
+                .lines()
+                .map(|value| value.parse().unwrap())
+                .collect();
+            assert_eq!(ids.len(), CHILD_IDS);
+            assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+            for id in ids {
+                assert!(created.insert(id));
+            }
+        }
+        assert_eq!(created.len(), 4 * CHILD_IDS);
+        assert_eq!(created.iter().min(), Some(&1001));
+        let generator = SharedGenerator::<Id>::open(&path).unwrap();
+        assert_eq!(
+            generator.current_value().unwrap(),
+            1000 + 4 * CHILD_IDS as u64
+        );
+        assert_eq!(Id::current_value(), 1000);
+        fs::remove_dir_all(&dir).unwrap();
+        println!("shared_processes ... ok");
+    }
+}
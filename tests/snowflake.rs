@@ -0,0 +1,108 @@
+use numid::{numid, MockClock};
+use std::thread;
+
+#[test]
+fn snowflake_parts() {
+    static CLOCK: MockClock = MockClock::new(1_700_000_000_000);
+    numid!(@Snowflake(node_bits = 10, seq_bits = 12) struct Id);
+
+    assert_eq!(Id::TIMESTAMP_BITS, 42);
+    Id::set_clock(&CLOCK);
+    Id::set_node(513);
+
+    let id = Id::new();
+    assert_eq!(
+        (id.timestamp(), id.node(), id.sequence()),
+        (1_700_000_000_000, 513, 0)
+    );
+    assert_eq!(Id::new().sequence(), 1);
+    CLOCK.advance(1);
+    let next = Id::new();
+    assert_eq!((next.timestamp(), next.sequence()), (1_700_000_000_001, 0));
+    assert!(next > id);
+
+    assert_eq!(Id::from_parts(id.timestamp(), id.node(), id.sequence()), id);
+    assert_eq!(Id::from_parts(3, 2, 1).value(), 3 << 22 | 2 << 12 | 1);
+}
+
+#[test]
+fn snowflake_clock_backwards() {
+    static CLOCK: MockClock = MockClock::new(100);
+    numid!(@Snowflake(node_bits = 4, seq_bits = 2) struct Id);
+
+    Id::set_clock(&CLOCK);
+    let mut last = Id::new();
+    CLOCK.set(50);
+    // 3 ids left in the millisecond 100, then the timestamp is incremented.
+    for expected in &[(100, 1), (100, 2), (100, 3), (101, 0), (101, 1)] {
+        let id = Id::new();
+        assert!(id > last);
+        assert_eq!((id.timestamp(), id.sequence()), *expected);
+        last = id;
+    }
+    CLOCK.set(200);
+    assert_eq!(Id::new().timestamp(), 200);
+}
+
+#[test]
+fn snowflake_exhausted() {
+    static CLOCK: MockClock = MockClock::new(u64::MAX);
+    numid!(@Snowflake struct Id);
+
+    Id::set_clock(&CLOCK);
+    assert_eq!(Id::try_new(), Err(numid::Error::Exhausted));
+}
+
+#[test]
+#[should_panic(expected = "the node id doesn't fit in 3 bits")]
+fn snowflake_node_too_large() {
+    numid!(@Snowflake(node_bits = 3, seq_bits = 12) struct Id);
+
+    Id::set_node(8);
+}
+
+#[test]
+fn snowflake_threads() {
+    static CLOCK: MockClock = MockClock::new(0);
+    numid!(@Snowflake(node_bits = 10, seq_bits = 8) struct Id);
+
+    Id::set_clock(&CLOCK);
+    let handles: Vec<_> = (0..8)
+        .map(|_| {
+            thread::spawn(|| {
+                let ids: Vec<Id> = (0..1000).map(|_| Id::new()).collect();
+                assert!(ids.windows(2).all(|w| w[0] < w[1]));
+                ids
+            })
+        })
+        .collect();
+
+    let mut ids: Vec<Id> = handles
+        .into_iter()
+        .flat_map(|handle| handle.join().unwrap())
+        .collect();
+    ids.sort();
+    ids.dedup();
+    assert_eq!(ids.len(), 8000);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn snowflake_system_clock() {
+    use numid::Clock;
+
+    numid!(@Snowflake(node_bits = 10, seq_bits = 12) pub struct Id);
+
+    let before = numid::SystemClock.now_millis();
+    let id = Id::new();
+    assert!(id.timestamp() >= before);
+    assert!(id.timestamp() <= numid::SystemClock.now_millis());
+}
+
+#[cfg(not(feature = "std"))]
+#[test]
+fn snowflake_no_clock() {
+    numid!(@Snowflake struct Id);
+
+    assert_eq!(Id::try_new(), Err(numid::Error::NoClock));
+}
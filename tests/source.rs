@@ -0,0 +1,137 @@
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use numid::{numid, Counter, CounterSnapshot, Error, IdSource, Overflow, Primitive};
+
+/// A source which counts the requests and can be made to fail.
+struct MockSource {
+    counter: Counter<u32>,
+    requests: AtomicUsize,
+    fail: AtomicBool,
+}
+
+impl MockSource {
+    const fn new(initial: u32) -> MockSource {
+        MockSource {
+            counter: Counter::from_atomic(<<u32 as Primitive>::Atomic>::new(initial)),
+            requests: AtomicUsize::new(0),
+            fail: AtomicBool::new(false),
+        }
+    }
+
+    fn request(&self) -> Result<(), Error> {
+        self.requests.fetch_add(1, Ordering::SeqCst);
+        if self.fail.load(Ordering::SeqCst) {
+            Err(Error::Exhausted)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl IdSource<u32> for MockSource {
+    fn next(&self, overflow: Overflow, initial: u32, step: u32) -> Result<u32, Error> {
+        self.request()?;
+        self.counter.try_next(overflow, initial, step)
+    }
+
+    fn reserve_block(
+        &self,
+        count: usize,
+        overflow: Overflow,
+        initial: u32,
+        step: u32,
+    ) -> Result<u32, Error> {
+        self.request()?;
+        self.counter.try_reserve(count, overflow, initial, step)
+    }
+
+    fn observe_max(&self, value: u32) -> bool {
+        self.counter.replace_current_value(value)
+    }
+
+    fn current(&self) -> u32 {
+        self.counter.current_value()
+    }
+}
+
+static MOCK: MockSource = MockSource::new(100);
+
+numid!(@Source(MOCK) @Step(2) struct MockId(u32) -> 100);
+
+#[test]
+fn source_mock() {
+    assert_eq!(MockId::new().value(), 102);
+    assert_eq!(
+        MockId::reserve(3).map(MockId::value).collect::<Vec<_>>(),
+        vec![104, 106, 108]
+    );
+    assert_eq!(MockId::current_value(), 108);
+    assert_eq!(MOCK.requests.load(Ordering::SeqCst), 2);
+
+    assert!(MockId::replace_current_value(200));
+    assert_eq!(MOCK.current(), 200);
+    assert_eq!(MockId::epoch(), 0);
+    assert!(!MockId::restore(CounterSnapshot {
+        current_value: 150,
+        epoch: 3
+    }));
+    assert_eq!(
+        MockId::snapshot(),
+        CounterSnapshot {
+            current_value: 200,
+            epoch: 0
+        }
+    );
+
+    MOCK.fail.store(true, Ordering::SeqCst);
+    assert_eq!(MockId::try_new(), Err(Error::Exhausted));
+    assert!(MockId::try_reserve(2).is_err());
+    MOCK.fail.store(false, Ordering::SeqCst);
+    assert_eq!(MockId::new().value(), 202);
+}
+
+mod shared {
+    use numid::{numid, Counter, Primitive};
+
+    pub static SOURCE: Counter<u64> = Counter::from_atomic(<<u64 as Primitive>::Atomic>::new(0));
+
+    numid!(@Source(self::SOURCE) pub struct UserId);
+    numid!(@Source(crate::shared::SOURCE) pub struct OrderId);
+}
+
+#[test]
+fn source_shared() {
+    use shared::{OrderId, UserId};
+
+    let user = UserId::new();
+    let order = OrderId::new();
+    assert_eq!((user.value(), order.value()), (1, 2));
+    assert_eq!(UserId::current_value(), 2);
+    assert_eq!(shared::SOURCE.current_value(), 2);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn source_thread_cached() {
+    static SOURCE: MockSource = MockSource::new(0);
+    numid!(@Source(SOURCE) @ThreadCached(10) struct Id(u32));
+
+    let ids: Vec<u32> = (0..25).map(|_| Id::new().value()).collect();
+    assert_eq!(ids, (1..=25).collect::<Vec<_>>());
+    assert_eq!(SOURCE.requests.load(Ordering::SeqCst), 3);
+    assert_eq!(Id::current_value(), 30);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn source_pool() {
+    static SOURCE: MockSource = MockSource::new(0);
+    numid!(@Pool @Source(SOURCE) struct Id(u32));
+
+    let a = Id::alloc().unwrap();
+    let b = Id::alloc().unwrap();
+    assert!(Id::free(a));
+    assert_eq!(Id::alloc(), Ok(a));
+    assert_eq!(Id::alloc().map(Id::value), Ok(b.value() + 1));
+    assert_eq!(SOURCE.requests.load(Ordering::SeqCst), 3);
+}
@@ -0,0 +1,191 @@
+#![cfg(feature = "rusqlite")]
+
+use std::collections::HashSet;
+use std::fs;
+use std::sync::Arc;
+use std::thread;
+
+use numid::{numid, Error, IdSource, Overflow, SqliteSource};
+use rusqlite::Connection;
+
+mod common;
+use common::temp_dir;
+
+fn stored(connection: &Connection, name: &str) -> Option<String> {
+    connection
+        .query_row(
+            "SELECT value FROM numid_sequences WHERE name = ?1",
+            [name],
+            |row| row.get(0),
+        )
+        .ok()
+}
+
+#[test]
+fn sqlite_blocks() {
+    static SOURCE: SqliteSource<u64> = SqliteSource::new("id", 100, 10);
+    numid!(@Source(SOURCE) struct Id -> 100);
+
+    assert_eq!(Id::try_new(), Err(Error::Unavailable));
+    assert!(SOURCE.take_error().is_none());
+
+    SOURCE
+        .set_connection(Connection::open_in_memory().unwrap())
+        .unwrap();
+    assert_eq!(Id::current_value(), 100);
+    assert_eq!(Id::new().value(), 101);
+    assert_eq!(SOURCE.ceiling(), 110);
+    for value in 102..=110 {
+        assert_eq!(Id::new().value(), value);
+    }
+    assert_eq!(Id::new().value(), 111);
+    assert_eq!(SOURCE.ceiling(), 120);
+
+    // The block reserved continues the block of the source.
+    let block: Vec<u64> = Id::reserve(15).map(Id::value).collect();
+    assert_eq!(block, (112..=126).collect::<Vec<_>>());
+    assert_eq!(SOURCE.ceiling(), 126);
+    assert_eq!(Id::current_value(), 126);
+
+    assert!(Id::replace_current_value(200));
+    assert!(!Id::replace_current_value(150));
+    assert_eq!(SOURCE.ceiling(), 200);
+    assert_eq!(Id::new().value(), 201);
+    assert_eq!(SOURCE.ceiling(), 210);
+
+    let connection = SOURCE.take_connection().unwrap();
+    assert_eq!(stored(&connection, "id").as_deref(), Some("210"));
+    assert_eq!(Id::try_new(), Err(Error::Unavailable));
+}
+
+#[test]
+fn sqlite_restart() {
+    static FIRST: SqliteSource<u32> = SqliteSource::new("user", 0, 100);
+    static SECOND: SqliteSource<u32> = SqliteSource::new("user", 0, 100);
+    static ORDERS: SqliteSource<u32> = SqliteSource::new("order", 0, 100);
+    numid!(@Source(FIRST) struct Before(u32));
+    numid!(@Source(SECOND) struct After(u32));
+    numid!(@Source(ORDERS) struct Order(u32));
+
+    let dir = temp_dir("sqlite-restart");
+    let path = dir.join("ids.db");
+    FIRST.open(&path).unwrap();
+    ORDERS.open(&path).unwrap();
+    assert_eq!(Before::new().value(), 1);
+    assert_eq!(Before::new().value(), 2);
+    assert_eq!(Order::new().value(), 1);
+    drop(FIRST.take_connection());
+
+    // The values left in the block of the first run are skipped.
+    SECOND.open(&path).unwrap();
+    assert_eq!(After::current_value(), 100);
+    assert_eq!(After::new().value(), 101);
+
+    let connection = Connection::open(&path).unwrap();
+    assert_eq!(stored(&connection, "user").as_deref(), Some("200"));
+    assert_eq!(stored(&connection, "order").as_deref(), Some("100"));
+    drop(connection);
+    drop(SECOND.take_connection());
+    drop(ORDERS.take_connection());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn sqlite_connections() {
+    let dir = temp_dir("sqlite-connections");
+    let path = dir.join("ids.db");
+    // The table is created before the threads, creating it concurrently fails with `SQLITE_BUSY`.
+    SqliteSource::<u64>::new("id", 0, 1).open(&path).unwrap();
+
+    let sources: Vec<_> = (0..4)
+        .map(|_| {
+            let source = SqliteSource::<u64>::new("id", 0, 7);
+            let connection = Connection::open(&path).unwrap();
+            connection
+                .busy_timeout(std::time::Duration::from_secs(10))
+                .unwrap();
+            source.set_connection(connection).unwrap();
+            Arc::new(source)
+        })
+        .collect();
+    let threads: Vec<_> = sources
+        .iter()
+        .cloned()
+        .map(|source| {
+            thread::spawn(move || {
+                (0..100)
+                    .map(|_| source.next(Overflow::Panic, 0, 1).unwrap())
+                    .collect::<Vec<_>>()
+            })
+        })
+        .collect();
+
+    let mut created = HashSet::new();
+    for thread in threads {
+        let values = thread.join().unwrap();
+        assert!(values.windows(2).all(|pair| pair[0] < pair[1]));
+        for value in values {
+            assert!(created.insert(value));
+        }
+    }
+    assert_eq!(created.len(), 400);
+    drop(sources);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn sqlite_errors() {
+    static SOURCE: SqliteSource<u8> = SqliteSource::new("small", 0, 100);
+    numid!(@Source(SOURCE) @Step(3) struct Small(u8));
+
+    let connection = Connection::open_in_memory().unwrap();
+    connection
+        .execute_batch(
+            "CREATE TABLE numid_sequences (name TEXT PRIMARY KEY NOT NULL, value TEXT NOT NULL);
+             INSERT INTO numid_sequences VALUES ('small', '240');",
+        )
+        .unwrap();
+    SOURCE.set_connection(connection).unwrap();
+    assert_eq!(Small::new().value(), 243);
+    assert_eq!(SOURCE.ceiling(), 255);
+    assert_eq!(Small::reserve(4).map(Small::value).next_back(), Some(255));
+    assert_eq!(Small::try_new(), Err(Error::Exhausted));
+    assert!(Small::try_reserve(1).is_err());
+
+    let connection = SOURCE.take_connection().unwrap();
+    connection
+        .execute("UPDATE numid_sequences SET value = 'x'", [])
+        .unwrap();
+    assert!(SOURCE.set_connection(connection).is_err());
+
+    let dir = temp_dir("sqlite-errors");
+    let path = dir.join("ids.db");
+    let source = SqliteSource::<u8>::new("small", 0, 2);
+    source.open(&path).unwrap();
+    assert_eq!(source.next(Overflow::Panic, 0, 1), Ok(1));
+    Connection::open(&path)
+        .unwrap()
+        .execute_batch("DROP TABLE numid_sequences")
+        .unwrap();
+    assert_eq!(source.next(Overflow::Panic, 0, 1), Ok(2));
+    assert_eq!(
+        source.next(Overflow::Saturate, 0, 1),
+        Err(Error::Unavailable)
+    );
+    assert!(source.take_error().is_some());
+    assert!(source.take_error().is_none());
+    drop(source);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "INITIAL_VALUE")]
+fn sqlite_initial_value() {
+    static SOURCE: SqliteSource<u64> = SqliteSource::new("id", 0, 10);
+    numid!(@Source(SOURCE) struct Id -> 100);
+
+    SOURCE
+        .set_connection(Connection::open_in_memory().unwrap())
+        .unwrap();
+    Id::new();
+}
@@ -0,0 +1,60 @@
+#![cfg(feature = "std")]
+
+use numid::numid;
+use std::collections::HashSet;
+use std::thread;
+
+#[test]
+fn thread_cached_block() {
+    numid!(@ThreadCached(10) struct Id(u32) -> 100);
+
+    let id = Id::new();
+    assert_eq!(id.value(), 101);
+    assert_eq!(Id::current_value(), 110);
+    for value in 102..=110 {
+        assert_eq!(Id::new().value(), value);
+    }
+    assert_eq!(Id::try_new().unwrap().value(), 111);
+    assert_eq!(Id::current_value(), 120);
+
+    let other = thread::spawn(Id::new).join().unwrap();
+    assert_eq!(other.value(), 121);
+    assert_eq!(Id::new().value(), 112);
+}
+
+#[test]
+fn thread_cached_unique() {
+    numid!(@ThreadCached(64) struct Id(u64));
+
+    let handles: Vec<_> = (0..8)
+        .map(|_| {
+            thread::spawn(|| {
+                let ids: Vec<_> = (0..1000).map(|_| Id::new()).collect();
+                // ordered inside a thread
+                assert!(ids.windows(2).all(|w| w[0] < w[1]));
+                ids
+            })
+        })
+        .collect();
+
+    let mut ids = HashSet::new();
+    for handle in handles {
+        for id in handle.join().unwrap() {
+            assert!(ids.insert(id), "duplicate id : {:?}", id);
+        }
+    }
+    assert_eq!(ids.len(), 8000);
+}
+
+#[test]
+fn thread_cached_exhausted() {
+    numid!(@ThreadCached(4) struct Id(u8) -> 249);
+
+    assert_eq!(Id::new().value(), 250);
+    assert_eq!(Id::current_value(), 253);
+    let _ = Id::reserve(0);
+    for value in 251..=255 {
+        assert_eq!(Id::try_new().unwrap().value(), value);
+    }
+    assert_eq!(Id::try_new(), Err(numid::Error::Exhausted));
+}
@@ -0,0 +1,56 @@
+use numid::numid;
+use std::collections::HashSet;
+use std::thread;
+
+macro_rules! test_threads {
+    ($func:ident, $ty:ty) => {
+        #[test]
+        fn $func() {
+            numid!(struct Id($ty));
+
+            let handles: Vec<_> = (0..8)
+                .map(|_| thread::spawn(|| (0..1000).map(|_| Id::new()).collect::<Vec<_>>()))
+                .collect();
+
+            let mut ids = HashSet::new();
+            for handle in handles {
+                for id in handle.join().unwrap() {
+                    assert!(ids.insert(id), "duplicate id : {:?}", id);
+                }
+            }
+
+            assert_eq!(ids.len(), 8000);
+            assert_eq!(Id::current_value(), 8000);
+        }
+    };
+}
+
+test_threads!(threads_u16, u16);
+test_threads!(threads_u32, u32);
+test_threads!(threads_u64, u64);
+test_threads!(threads_i64, i64);
+test_threads!(threads_u128, u128);
+
+#[test]
+fn threads_create_maybe() {
+    numid!(struct Id(u128));
+
+    let handles: Vec<_> = (0..8)
+        .map(|_| {
+            thread::spawn(|| {
+                (1..=1000u128)
+                    .filter_map(Id::create_maybe)
+                    .collect::<Vec<_>>()
+            })
+        })
+        .collect();
+
+    let mut ids = HashSet::new();
+    for handle in handles {
+        for id in handle.join().unwrap() {
+            assert!(ids.insert(id), "duplicate id : {:?}", id);
+        }
+    }
+
+    assert_eq!(Id::current_value(), 1000);
+}